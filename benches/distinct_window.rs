@@ -0,0 +1,44 @@
+use advent_of_code_2022::data_structure::{first_distinct_window, RingBuffer};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// The approach day 6 used to take, checking every pair in the window after each push
+fn ring_buffer_approach(stream: &str, size: usize) -> Option<usize> {
+    let mut buffer = RingBuffer::with_capacity(size);
+    for (i, c) in stream.chars().enumerate() {
+        buffer.push(c);
+        if buffer.is_full() && !buffer.contains_duplicates() {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+/// A stream with no distinct window of `size` until the very end. Each window has one repeated
+/// pair near its end, which is the worst case for checking every pair.
+fn stream_for(size: usize) -> String {
+    let alphabet: Vec<char> = ('a'..='z').collect();
+    let mut pattern = alphabet[..size - 2].to_vec();
+    pattern.push(pattern[size - 3]);
+    let mut stream: String = pattern.iter().cycle().take(100_000).collect();
+    stream.extend(&alphabet[..size]);
+    stream
+}
+
+fn bench_distinct_window(c: &mut Criterion) {
+    let mut group = c.benchmark_group("first_distinct_window");
+    for size in [4, 14, 26] {
+        let stream = stream_for(size);
+        group.bench_with_input(BenchmarkId::new("ring_buffer", size), &stream, |b, s| {
+            b.iter(|| ring_buffer_approach(black_box(s), size))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("distinct_window", size),
+            &stream,
+            |b, s| b.iter(|| first_distinct_window(black_box(s).chars(), size)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_distinct_window);
+criterion_main!(benches);
@@ -0,0 +1,224 @@
+//! Derives `FromStr` for structs from a line format, e.g. `#[line("move {amount} from {from} to {to}")]`.
+//!
+//! The generated code uses the helpers in `advent_of_code_2022::input::line_format`, which is
+//! where the derive is documented and tested.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use std::collections::HashMap;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Member};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Splits a format string into literal text and field names
+fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("Unclosed '{' in line format".to_string()),
+                    }
+                }
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err(
+                        "Fields in a line format must be named, e.g. {amount} or {0}".into(),
+                    );
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                if let Some(Segment::Field(previous)) = segments.last() {
+                    return Err(format!(
+                        "Fields {{{}}} and {{{}}} need some text between them",
+                        previous, name
+                    ));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("Unmatched '}' in line format, use '}}' for a literal brace".into()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Implements `FromStr` using the format given in a `#[line("...")]` attribute. Errors are
+/// `Diagnostic`s pointing at the part of the line that could not be parsed.
+#[proc_macro_derive(FromLine, attributes(line))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromLine can only be derived for structs",
+            ))
+        }
+    };
+
+    let attribute = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("line"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                "FromLine needs a format, e.g. #[line(\"{x},{y}\")]",
+            )
+        })?;
+    let format: LitStr = attribute.parse_args()?;
+    let segments =
+        parse_format(&format.value()).map_err(|e| syn::Error::new_spanned(&format, e))?;
+
+    // Every field in the struct, by the name used for it in the format
+    let mut members: HashMap<String, (Member, &syn::Type)> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => (ident.to_string(), (Member::Named(ident.clone()), &field.ty)),
+            None => (i.to_string(), (Member::Unnamed(i.into()), &field.ty)),
+        })
+        .collect();
+
+    let mut steps = Vec::new();
+    let mut assignments = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => steps.push(quote! {
+                __line_format::expect_literal(s, &mut position, #literal)?;
+            }),
+            Segment::Field(field) => {
+                let (member, ty) = members.remove(field).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &format,
+                        format!("{{{}}} is not a field of {}, or is used twice", field, name),
+                    )
+                })?;
+                let next = match segments.get(i + 1) {
+                    Some(Segment::Literal(literal)) => quote! { Some(#literal) },
+                    _ => quote! { None },
+                };
+                let variable = format_ident!("__field_{}", i, span = Span::call_site());
+                steps.push(quote! {
+                    let span = __line_format::take_field(s, &mut position, #next)?;
+                    let #variable = __line_format::parse_field::<#ty>(s, span, #field)?;
+                });
+                assignments.push(quote! { #member: #variable });
+            }
+        }
+    }
+
+    if let Some(unused) = members.keys().min() {
+        return Err(syn::Error::new_spanned(
+            &format,
+            format!(
+                "Field {} of {} is missing from the line format",
+                unused, name
+            ),
+        ));
+    }
+
+    let construct = match fields {
+        Fields::Unit => quote! { Self },
+        _ => quote! { Self { #(#assignments),* } },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::advent_of_code_2022::input::diagnostic::Diagnostic;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                use ::advent_of_code_2022::input::line_format as __line_format;
+                // Whitespace around the line is ignored, but spans still count from its start
+                let s = s.trim_end();
+                let mut position = s.len() - s.trim_start().len();
+                #(#steps)*
+                __line_format::expect_end(s, position)?;
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fields_and_literals() {
+        assert_eq!(
+            parse_format("{x},{{{y}}}"),
+            Ok(vec![
+                Segment::Field("x".to_string()),
+                Segment::Literal(",{".to_string()),
+                Segment::Field("y".to_string()),
+                Segment::Literal("}".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_brace() {
+        assert_eq!(
+            parse_format("move {amount"),
+            Err("Unclosed '{' in line format".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unmatched_brace() {
+        assert!(parse_format("{x}}")
+            .unwrap_err()
+            .starts_with("Unmatched '}'"));
+    }
+
+    #[test]
+    fn rejects_unnamed_field() {
+        assert!(parse_format("{x},{ }")
+            .unwrap_err()
+            .starts_with("Fields in a line format"));
+    }
+
+    #[test]
+    fn rejects_adjacent_fields() {
+        assert_eq!(
+            parse_format("{x}{y}"),
+            Err("Fields {x} and {y} need some text between them".to_string())
+        );
+    }
+}
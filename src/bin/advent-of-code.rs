@@ -1,7 +1,37 @@
+use advent_of_code_2022::d07::report::Report;
+use advent_of_code_2022::input::compressed::decompressed;
+use advent_of_code_2022::input::diagnostic::Diagnostic;
+use advent_of_code_2022::input::normalised::Normalised;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-enum Opt {
+struct Opt {
+    /// Read the puzzle input from this file instead of stdin. Gzip and zstd files are decompressed
+    #[structopt(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// Use colour when reporting problems with the puzzle input
+    #[structopt(long)]
+    colour: bool,
+
+    /// Keep whitespace at the end of lines instead of removing it
+    #[structopt(long)]
+    keep_trailing_whitespace: bool,
+
+    /// Show a report of what was read from the puzzle input instead of solving it. Day 7 can show
+    /// the filesystem as a `tree` or sizes like `du`
+    #[structopt(long)]
+    report: Option<Report>,
+
+    #[structopt(subcommand)]
+    solution: Solution,
+}
+
+#[derive(Debug, Copy, Clone, StructOpt)]
+enum Solution {
     D01p1,
     D01p2,
     D02p1,
@@ -54,61 +84,98 @@ enum Opt {
     D25p2,
 }
 
+fn solve<R: BufRead>(solution: Solution, read: R) -> Result<String, Diagnostic> {
+    match solution {
+        Solution::D01p1 => advent_of_code_2022::d01::p1::run(read),
+        Solution::D01p2 => advent_of_code_2022::d01::p2::run(read),
+        Solution::D02p1 => advent_of_code_2022::d02::p1::run(read),
+        Solution::D02p2 => advent_of_code_2022::d02::p2::run(read),
+        Solution::D03p1 => advent_of_code_2022::d03::p1::run(read),
+        Solution::D03p2 => advent_of_code_2022::d03::p2::run(read),
+        Solution::D04p1 => advent_of_code_2022::d04::p1::run(read),
+        Solution::D04p2 => advent_of_code_2022::d04::p2::run(read),
+        Solution::D05p1 => advent_of_code_2022::d05::p1::run(read),
+        Solution::D05p2 => advent_of_code_2022::d05::p2::run(read),
+        Solution::D06p1 => advent_of_code_2022::d06::p1::run(read),
+        Solution::D06p2 => advent_of_code_2022::d06::p2::run(read),
+        Solution::D07p1 => advent_of_code_2022::d07::p1::run(read),
+        Solution::D07p2 => advent_of_code_2022::d07::p2::run(read),
+        Solution::D08p1 => advent_of_code_2022::d08::p1::run(read),
+        Solution::D08p2 => advent_of_code_2022::d08::p2::run(read),
+        Solution::D09p1 => advent_of_code_2022::d09::p1::run(read),
+        Solution::D09p2 => advent_of_code_2022::d09::p2::run(read),
+        Solution::D10p1 => advent_of_code_2022::d10::p1::run(read),
+        Solution::D10p2 => advent_of_code_2022::d10::p2::run(read),
+        Solution::D11p1 => advent_of_code_2022::d11::p1::run(read),
+        Solution::D11p2 => advent_of_code_2022::d11::p2::run(read),
+        Solution::D12p1 => advent_of_code_2022::d12::p1::run(read),
+        Solution::D12p2 => advent_of_code_2022::d12::p2::run(read),
+        Solution::D13p1 => advent_of_code_2022::d13::p1::run(read),
+        Solution::D13p2 => advent_of_code_2022::d13::p2::run(read),
+        Solution::D14p1 => advent_of_code_2022::d14::p1::run(read),
+        Solution::D14p2 => advent_of_code_2022::d14::p2::run(read),
+        Solution::D15p1 => advent_of_code_2022::d15::p1::run(read),
+        Solution::D15p2 => advent_of_code_2022::d15::p2::run(read),
+        Solution::D16p1 => advent_of_code_2022::d16::p1::run(read),
+        Solution::D16p2 => advent_of_code_2022::d16::p2::run(read),
+        Solution::D17p1 => advent_of_code_2022::d17::p1::run(read),
+        Solution::D17p2 => advent_of_code_2022::d17::p2::run(read),
+        Solution::D18p1 => advent_of_code_2022::d18::p1::run(read),
+        Solution::D18p2 => advent_of_code_2022::d18::p2::run(read),
+        Solution::D19p1 => advent_of_code_2022::d19::p1::run(read),
+        Solution::D19p2 => advent_of_code_2022::d19::p2::run(read),
+        Solution::D20p1 => advent_of_code_2022::d20::p1::run(read),
+        Solution::D20p2 => advent_of_code_2022::d20::p2::run(read),
+        Solution::D21p1 => advent_of_code_2022::d21::p1::run(read),
+        Solution::D21p2 => advent_of_code_2022::d21::p2::run(read),
+        Solution::D22p1 => advent_of_code_2022::d22::p1::run(read),
+        Solution::D22p2 => advent_of_code_2022::d22::p2::run(read),
+        Solution::D23p1 => advent_of_code_2022::d23::p1::run(read),
+        Solution::D23p2 => advent_of_code_2022::d23::p2::run(read),
+        Solution::D24p1 => advent_of_code_2022::d24::p1::run(read),
+        Solution::D24p2 => advent_of_code_2022::d24::p2::run(read),
+        Solution::D25p1 => advent_of_code_2022::d25::p1::run(read),
+        Solution::D25p2 => advent_of_code_2022::d25::p2::run(read),
+    }
+}
+
 fn main() {
-    let stdin = std::io::stdin();
-    let lock = stdin.lock();
     let opt = Opt::from_args();
-    let output = match opt {
-        Opt::D01p1 => advent_of_code_2022::d01::p1::run(lock),
-        Opt::D01p2 => advent_of_code_2022::d01::p2::run(lock),
-        Opt::D02p1 => advent_of_code_2022::d02::p1::run(lock),
-        Opt::D02p2 => advent_of_code_2022::d02::p2::run(lock),
-        Opt::D03p1 => advent_of_code_2022::d03::p1::run(lock),
-        Opt::D03p2 => advent_of_code_2022::d03::p2::run(lock),
-        Opt::D04p1 => advent_of_code_2022::d04::p1::run(lock),
-        Opt::D04p2 => advent_of_code_2022::d04::p2::run(lock),
-        Opt::D05p1 => advent_of_code_2022::d05::p1::run(lock),
-        Opt::D05p2 => advent_of_code_2022::d05::p2::run(lock),
-        Opt::D06p1 => advent_of_code_2022::d06::p1::run(lock),
-        Opt::D06p2 => advent_of_code_2022::d06::p2::run(lock),
-        Opt::D07p1 => advent_of_code_2022::d07::p1::run(lock),
-        Opt::D07p2 => advent_of_code_2022::d07::p2::run(lock),
-        Opt::D08p1 => advent_of_code_2022::d08::p1::run(lock),
-        Opt::D08p2 => advent_of_code_2022::d08::p2::run(lock),
-        Opt::D09p1 => advent_of_code_2022::d09::p1::run(lock),
-        Opt::D09p2 => advent_of_code_2022::d09::p2::run(lock),
-        Opt::D10p1 => advent_of_code_2022::d10::p1::run(lock),
-        Opt::D10p2 => advent_of_code_2022::d10::p2::run(lock),
-        Opt::D11p1 => advent_of_code_2022::d11::p1::run(lock),
-        Opt::D11p2 => advent_of_code_2022::d11::p2::run(lock),
-        Opt::D12p1 => advent_of_code_2022::d12::p1::run(lock),
-        Opt::D12p2 => advent_of_code_2022::d12::p2::run(lock),
-        Opt::D13p1 => advent_of_code_2022::d13::p1::run(lock),
-        Opt::D13p2 => advent_of_code_2022::d13::p2::run(lock),
-        Opt::D14p1 => advent_of_code_2022::d14::p1::run(lock),
-        Opt::D14p2 => advent_of_code_2022::d14::p2::run(lock),
-        Opt::D15p1 => advent_of_code_2022::d15::p1::run(lock),
-        Opt::D15p2 => advent_of_code_2022::d15::p2::run(lock),
-        Opt::D16p1 => advent_of_code_2022::d16::p1::run(lock),
-        Opt::D16p2 => advent_of_code_2022::d16::p2::run(lock),
-        Opt::D17p1 => advent_of_code_2022::d17::p1::run(lock),
-        Opt::D17p2 => advent_of_code_2022::d17::p2::run(lock),
-        Opt::D18p1 => advent_of_code_2022::d18::p1::run(lock),
-        Opt::D18p2 => advent_of_code_2022::d18::p2::run(lock),
-        Opt::D19p1 => advent_of_code_2022::d19::p1::run(lock),
-        Opt::D19p2 => advent_of_code_2022::d19::p2::run(lock),
-        Opt::D20p1 => advent_of_code_2022::d20::p1::run(lock),
-        Opt::D20p2 => advent_of_code_2022::d20::p2::run(lock),
-        Opt::D21p1 => advent_of_code_2022::d21::p1::run(lock),
-        Opt::D21p2 => advent_of_code_2022::d21::p2::run(lock),
-        Opt::D22p1 => advent_of_code_2022::d22::p1::run(lock),
-        Opt::D22p2 => advent_of_code_2022::d22::p2::run(lock),
-        Opt::D23p1 => advent_of_code_2022::d23::p1::run(lock),
-        Opt::D23p2 => advent_of_code_2022::d23::p2::run(lock),
-        Opt::D24p1 => advent_of_code_2022::d24::p1::run(lock),
-        Opt::D24p2 => advent_of_code_2022::d24::p2::run(lock),
-        Opt::D25p1 => advent_of_code_2022::d25::p1::run(lock),
-        Opt::D25p2 => advent_of_code_2022::d25::p2::run(lock),
+    if opt.report.is_some() && !matches!(opt.solution, Solution::D07p1 | Solution::D07p2) {
+        structopt::clap::Error::with_description(
+            "--report is only available for day 7",
+            structopt::clap::ErrorKind::ArgumentConflict,
+        )
+        .exit();
+    }
+
+    let read: Box<dyn BufRead> = match &opt.input {
+        Some(path) => {
+            let file = File::open(path).expect("Could not open input file");
+            decompressed(BufReader::new(file), Some(path)).expect("Could not read input file")
+        }
+        None => decompressed(std::io::stdin().lock(), None).expect("Could not read input"),
     };
-    println!("{}", output)
+    let mut read = Normalised::from(read);
+    if !opt.keep_trailing_whitespace {
+        read = read.trim_trailing_whitespace();
+    }
+
+    let result = match opt.report {
+        Some(report) => advent_of_code_2022::d07::report::run(read, report),
+        None => solve(opt.solution, read),
+    };
+
+    match result {
+        Ok(output) => println!("{}", output),
+        Err(diagnostic) => {
+            let file = opt
+                .input
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "<stdin>".to_string());
+            eprint!("{}", diagnostic.with_file(file).render(opt.colour));
+            std::process::exit(1);
+        }
+    }
 }
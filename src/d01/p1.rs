@@ -1,13 +1,14 @@
+use crate::input::diagnostic::Diagnostic;
 use crate::input::grouped_input::GroupedInput;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let input = GroupedInput::from(buf_read);
     let max = input
         .map(|pack| pack.iter().sum())
         .reduce(usize::max)
         .expect("Something went wrong, there were no numbers");
-    format!("{}", max)
+    Ok(format!("{}", max))
 }
 
 #[cfg(test)]
@@ -18,7 +19,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "24000")
     }
 }
@@ -1,14 +1,19 @@
 use crate::domain::rock_paper_scissors::RockPaperScissorsGame;
+use crate::input::diagnostic::Diagnostic;
 use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let input = StringIter::<String, _>::from(buf_read);
     let score: usize = input
-        .map(|s| RockPaperScissorsGame::from_results_str(&s).unwrap())
-        .map(|g| g.score())
-        .sum();
-    format!("{}", score)
+        .enumerate()
+        .map(|(i, s)| {
+            RockPaperScissorsGame::from_results_str(&s)
+                .map(|g| g.score())
+                .map_err(|d| d.with_line_number(i + 1))
+        })
+        .sum::<Result<_, _>>()?;
+    Ok(format!("{}", score))
 }
 
 #[cfg(test)]
@@ -19,7 +24,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "12")
     }
 }
@@ -1,17 +1,20 @@
 use crate::domain::rucksack::Rucksack;
+use crate::input::diagnostic::Diagnostic;
 use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 use std::str::FromStr;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let input = StringIter::<String, _>::from(buf_read);
-    let rucksacks: Result<Vec<_>, _> = input.map(|r| Rucksack::from_str(&r)).collect();
-    let sum: usize = rucksacks
-        .unwrap()
-        .iter()
-        .map(|rs| rs.clashing_priority_value())
-        .sum();
-    format!("{}", sum)
+    let sum: usize = input
+        .enumerate()
+        .map(|(i, r)| {
+            Rucksack::from_str(&r)
+                .map(|rs| rs.clashing_priority_value())
+                .map_err(|d| d.with_line_number(i + 1))
+        })
+        .sum::<Result<_, _>>()?;
+    Ok(format!("{}", sum))
 }
 
 #[cfg(test)]
@@ -22,7 +25,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "157");
     }
 }
@@ -1,11 +1,17 @@
 use crate::domain::rucksack::{GroupRucksacks, Rucksack};
+use crate::input::diagnostic::Diagnostic;
 use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 use std::str::FromStr;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let input = StringIter::<String, _>::from(buf_read);
-    let mut rucksacks = input.map(|r| Rucksack::from_str(&r).unwrap()).peekable();
+    let mut rucksacks = input
+        .enumerate()
+        .map(|(i, r)| Rucksack::from_str(&r).map_err(|d| d.with_line_number(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .peekable();
     let mut groups = vec![];
     while rucksacks.peek().is_some() {
         groups.push(GroupRucksacks::from(
@@ -14,7 +20,7 @@ pub fn run<R: BufRead>(buf_read: R) -> String {
     }
 
     let sum: usize = groups.iter().map(|rs| rs.find_badge().priority()).sum();
-    format!("{}", sum)
+    Ok(format!("{}", sum))
 }
 
 #[cfg(test)]
@@ -25,7 +31,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "70")
     }
 }
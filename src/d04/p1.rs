@@ -1,15 +1,20 @@
 use crate::domain::search_party::SearchPair;
+use crate::input::diagnostic::Diagnostic;
 use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 use std::str::FromStr;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let input = StringIter::<String, _>::from(buf_read);
-    let sum = input
-        .map(|line| SearchPair::from_str(&line).unwrap())
+    let pairs = input
+        .enumerate()
+        .map(|(i, line)| SearchPair::from_str(&line).map_err(|d| d.with_line_number(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let sum = pairs
+        .iter()
         .filter(|sp| sp.contains_complete_overlap())
         .count();
-    format!("{}", sum)
+    Ok(format!("{}", sum))
 }
 
 #[cfg(test)]
@@ -20,7 +25,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "2");
     }
 }
@@ -1,21 +1,26 @@
 use crate::domain::crane9001::{Instruction, Stacks};
+use crate::input::diagnostic::Diagnostic;
 use crate::input::grouped_input_raw::GroupedInputRaw;
 use std::io::BufRead;
 use std::str::FromStr;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
     let mut input = GroupedInputRaw::from(buf_read);
     let crates = input.next().expect("No crates found");
+    // Instructions start after the crates and the blank line that follows them
+    let first_instruction_line = crates.len() + 2;
     let mut stacks = Stacks::from(crates);
-    input
-        .next()
-        .expect("No instructions found")
-        .into_iter()
-        .filter(|string| !string.is_empty())
-        .map(|string| Instruction::from_str(&string).unwrap())
-        .for_each(|instruction| stacks.instruct(instruction));
+    let instructions = input.next().expect("No instructions found");
+    for (i, string) in instructions.iter().enumerate() {
+        if string.is_empty() {
+            continue;
+        }
+        let instruction = Instruction::from_str(string)
+            .map_err(|d| d.with_line_number(first_instruction_line + i))?;
+        stacks.instruct(instruction);
+    }
 
-    stacks.get_message()
+    Ok(stacks.get_message())
 }
 
 #[cfg(test)]
@@ -26,7 +31,7 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "MCD");
     }
 }
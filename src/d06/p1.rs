@@ -1,18 +1,14 @@
-use crate::data_structure::RingBuffer;
-use crate::input::string_iter::StringIter;
+use crate::data_structure::first_distinct_window;
+use crate::input::byte_stream::ByteStream;
+use crate::input::diagnostic::Diagnostic;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(buf_read: R) -> String {
-    let stream = StringIter::<String, _>::from(buf_read).next().unwrap();
-    let data = stream.chars().enumerate();
-    let mut buffer = RingBuffer::with_capacity(4);
-    for (i, c) in data {
-        buffer.push(c);
-        if buffer.is_full() && !buffer.contains_duplicates() {
-            return (i + 1).to_string();
-        }
-    }
-    panic!("No marker found")
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
+    let stream = ByteStream::from(buf_read);
+    // The stream doesn't keep the bytes it has read, so there is no line to point at
+    first_distinct_window(stream, 4)
+        .map(|position| position.to_string())
+        .ok_or_else(|| Diagnostic::whole_line("No marker found", "").with_line_number(1))
 }
 
 #[cfg(test)]
@@ -23,19 +19,26 @@ mod tests {
     #[test]
     fn test_run() {
         let input = Cursor::new(include_str!("test-input.txt"));
-        let output = run(input);
+        let output = run(input).unwrap();
         assert_eq!(&output, "7");
     }
 
     #[test]
     fn additional_tests() {
         let input = Cursor::new("bvwbjplbgvbhsrlpgdmjqwftvncz");
-        assert_eq!(&run(input), "5");
+        assert_eq!(&run(input).unwrap(), "5");
         let input = Cursor::new("nppdvjthqldpwncqszvftbrmjlhg");
-        assert_eq!(&run(input), "6");
+        assert_eq!(&run(input).unwrap(), "6");
         let input = Cursor::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg");
-        assert_eq!(&run(input), "10");
+        assert_eq!(&run(input).unwrap(), "10");
         let input = Cursor::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw");
-        assert_eq!(&run(input), "11");
+        assert_eq!(&run(input).unwrap(), "11");
+    }
+
+    #[test]
+    fn no_marker() {
+        let error = run(Cursor::new("abcabcabc")).unwrap_err();
+        assert_eq!(error.message(), "No marker found");
+        assert_eq!(error.line_number(), Some(1));
     }
 }
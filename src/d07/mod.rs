@@ -1,2 +1,3 @@
 pub mod p1;
 pub mod p2;
+pub mod report;
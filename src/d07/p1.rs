@@ -1,18 +1,32 @@
+use crate::domain::filesystem::Transcript;
+use crate::input::diagnostic::Diagnostic;
+use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(_buf_read: R) -> String {
-    todo!()
+/// Directories at most this size count towards the answer
+const SMALL_DIRECTORY: u64 = 100000;
+
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
+    let filesystem = Transcript::replay_all(StringIter::<String, _>::from(buf_read))?;
+    let sizes = filesystem.sizes();
+    let answer = filesystem
+        .directories()
+        .map(|id| sizes[id])
+        .filter(|size| *size <= SMALL_DIRECTORY)
+        .sum::<u64>()
+        .to_string();
+    Ok(answer)
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
-    // use std::io::Cursor;
-    //
-    // #[test]
-    // fn test_run() {
-    //     let input = Cursor::new(include_str!("test-input.txt"));
-    //     let output = run(input);
-    //     assert_eq!(&output, "CMZ");
-    // }
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_run() {
+        let input = Cursor::new(include_str!("test-input.txt"));
+        let output = run(input).unwrap();
+        assert_eq!(&output, "95437");
+    }
 }
@@ -1,18 +1,37 @@
+use crate::domain::filesystem::Transcript;
+use crate::input::diagnostic::Diagnostic;
+use crate::input::string_iter::StringIter;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(_buf_read: R) -> String {
-    todo!()
+const DISK_SIZE: u64 = 70000000;
+const SPACE_NEEDED: u64 = 30000000;
+
+pub fn run<R: BufRead>(buf_read: R) -> Result<String, Diagnostic> {
+    let filesystem = Transcript::replay_all(StringIter::<String, _>::from(buf_read))?;
+    let sizes = filesystem.sizes();
+    // A transcript larger than the disk has no space to spare at all
+    let unused = DISK_SIZE.saturating_sub(sizes[filesystem.root()]);
+    let to_free = SPACE_NEEDED.saturating_sub(unused);
+    filesystem
+        .directories()
+        .map(|id| sizes[id])
+        .filter(|size| *size >= to_free)
+        .min()
+        .map(|size| size.to_string())
+        .ok_or_else(|| {
+            Diagnostic::whole_line("No directory is large enough to free the space needed", "")
+        })
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
-    // use std::io::Cursor;
-    //
-    // #[test]
-    // fn test_run() {
-    //     let input = Cursor::new(include_str!("test-input.txt"));
-    //     let output = run(input);
-    //     assert_eq!(&output, "CMZ");
-    // }
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_run() {
+        let input = Cursor::new(include_str!("test-input.txt"));
+        let output = run(input).unwrap();
+        assert_eq!(&output, "24933642");
+    }
 }
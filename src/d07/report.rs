@@ -0,0 +1,76 @@
+use crate::domain::filesystem::{human_size, Transcript};
+use crate::input::diagnostic::Diagnostic;
+use crate::input::string_iter::StringIter;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Ways of showing the filesystem rebuilt from a transcript, to check it was read correctly
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Report {
+    /// An indented listing like the `tree` command
+    Tree,
+    /// Every directory with its size, largest first
+    Du,
+}
+
+impl FromStr for Report {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tree" => Ok(Report::Tree),
+            "du" => Ok(Report::Du),
+            _ => Err(format!("Unknown report '{}', expected tree or du", s)),
+        }
+    }
+}
+
+pub fn run<R: BufRead>(buf_read: R, report: Report) -> Result<String, Diagnostic> {
+    let filesystem = Transcript::replay_all(StringIter::<String, _>::from(buf_read))?;
+    // The runner ends the output with a newline
+    Ok(match report {
+        Report::Tree => filesystem.tree_display().to_string().trim_end().to_string(),
+        Report::Du => filesystem
+            .disk_usage()
+            .into_iter()
+            .map(|(id, size)| format!("{}\t{}", human_size(size), filesystem.path(id)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_tree() {
+        let input = Cursor::new(include_str!("test-input.txt"));
+        let output = run(input, Report::Tree).unwrap();
+        assert_eq!(
+            output,
+            "/ (46.1M)\n\
+             ├── a/ (92.6K)\n\
+             │   ├── e/ (584)\n\
+             │   │   └── i (584)\n\
+             │   ├── f (28.4K)\n\
+             │   ├── g (2.5K)\n\
+             │   └── h.lst (61.1K)\n\
+             ├── b.txt (14.2M)\n\
+             ├── c.dat (8.1M)\n\
+             └── d/ (23.8M)\n    \
+             ├── j (3.9M)\n    \
+             ├── d.log (7.7M)\n    \
+             ├── d.ext (5.4M)\n    \
+             └── k (6.9M)"
+        );
+    }
+
+    #[test]
+    fn test_du() {
+        let input = Cursor::new(include_str!("test-input.txt"));
+        let output = run(input, Report::Du).unwrap();
+        assert_eq!(output, "46.1M\t/\n23.8M\t/d\n92.6K\t/a\n584\t/a/e");
+    }
+}
@@ -1,6 +1,7 @@
+use crate::input::diagnostic::Diagnostic;
 use std::io::BufRead;
 
-pub fn run<R: BufRead>(_buf_read: R) -> String {
+pub fn run<R: BufRead>(_buf_read: R) -> Result<String, Diagnostic> {
     todo!()
 }
 
@@ -12,7 +13,7 @@ mod tests {
     // #[test]
     // fn test_run() {
     //     let input = Cursor::new(include_str!("test-input.txt"));
-    //     let output = run(input);
+    //     let output = run(input).unwrap();
     //     assert_eq!(&output, "CMZ");
     // }
 }
@@ -1,11 +1,46 @@
+pub mod aggregate;
+pub mod bit_set;
+pub mod circular_list;
+pub mod cycle;
+pub mod disjoint_set;
+pub mod distinct_window;
+pub mod expr;
+pub mod geometry;
+pub mod grid;
+pub mod interval_set;
+pub mod memo;
+pub mod search;
+pub mod sparse_grid;
+pub mod tree;
+pub mod voxel_grid;
+
+pub use bit_set::{BitSet, BitSet64};
+pub use circular_list::CircularList;
+pub use disjoint_set::{DisjointSet, KeyedDisjointSet};
+pub use distinct_window::{first_distinct_window, DistinctWindow};
+pub use grid::Grid;
+pub use interval_set::IntervalSet;
+pub use memo::Memo;
+pub use sparse_grid::SparseGrid;
+pub use tree::Tree;
+pub use voxel_grid::VoxelGrid;
+
+use aggregate::Aggregate;
+use std::collections::vec_deque;
 use std::collections::VecDeque;
-use std::ops::Add;
+use std::ops::{Add, Index};
 
 // This file mostly copied from 2021
 
-pub struct RingBuffer<T> {
+/// A buffer that keeps the most recent data pushed to it, up to a fixed capacity.
+///
+/// A buffer can optionally keep track of an [`Aggregate`] of its data, such as a sum or minimum,
+/// which is updated as data is pushed and as it falls off the other end.
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T, A = ()> {
     buffer: VecDeque<T>,
     capacity: usize,
+    aggregate: A,
 }
 
 impl<T> RingBuffer<T> {
@@ -20,12 +55,34 @@ impl<T> RingBuffer<T> {
     /// assert_eq!(ring_buffer.capacity(), 2);
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
+        RingBuffer::with_aggregate(capacity, ())
+    }
+}
+
+impl<T, A: Aggregate<T>> RingBuffer<T, A> {
+    /// Creates a buffer with a given capacity that keeps track of an aggregate of its data
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::aggregate::Sum;
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_aggregate(2, Sum::default());
+    /// ring_buffer.extend(vec![1, 2, 3]);
+    /// assert_eq!(ring_buffer.aggregate(), 5);
+    /// ```
+    pub fn with_aggregate(capacity: usize, aggregate: A) -> Self {
         RingBuffer {
             buffer: VecDeque::with_capacity(capacity),
             capacity,
+            aggregate,
         }
     }
 
+    /// Gets the current value of the buffer's aggregate
+    pub fn aggregate(&self) -> A::Output {
+        self.aggregate.value()
+    }
+
     /// Get the capacity of the ring buffer. This value does not change
     /// ```rust
     /// use advent_of_code_2022::data_structure::RingBuffer;
@@ -104,14 +161,187 @@ impl<T> RingBuffer<T> {
     /// assert_eq!(ring_buffer.is_full(), true);
     /// ```
     pub fn push(&mut self, value: T) {
+        if self.capacity == 0 {
+            return;
+        }
         if self.is_full() {
-            self.buffer.pop_front();
+            self.pop_front();
         }
+        self.aggregate.push(&value);
         self.buffer.push_back(value);
     }
+
+    /// Removes and returns the oldest piece of data in the buffer
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_capacity(2);
+    /// ring_buffer.push(1);
+    /// ring_buffer.push(2);
+    /// ring_buffer.push(3);
+    /// assert_eq!(ring_buffer.pop_front(), Some(2));
+    /// assert_eq!(ring_buffer.pop_front(), Some(3));
+    /// assert_eq!(ring_buffer.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        let value = self.buffer.pop_front()?;
+        self.aggregate.evict(&value);
+        Some(value)
+    }
+
+    /// Gets the oldest piece of data in the buffer without removing it
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_capacity(2);
+    /// assert_eq!(ring_buffer.front(), None);
+    /// ring_buffer.push(1);
+    /// ring_buffer.push(2);
+    /// ring_buffer.push(3);
+    /// assert_eq!(ring_buffer.front(), Some(&2));
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        self.buffer.front()
+    }
+
+    /// Gets the newest piece of data in the buffer without removing it
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_capacity(2);
+    /// assert_eq!(ring_buffer.back(), None);
+    /// ring_buffer.push(1);
+    /// ring_buffer.push(2);
+    /// ring_buffer.push(3);
+    /// assert_eq!(ring_buffer.back(), Some(&3));
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        self.buffer.back()
+    }
+
+    /// Removes all data from the buffer. The capacity does not change
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_capacity(2);
+    /// ring_buffer.push(1);
+    /// ring_buffer.clear();
+    /// assert_eq!(ring_buffer.is_empty(), true);
+    /// assert_eq!(ring_buffer.capacity(), 2);
+    /// ```
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.aggregate.clear();
+    }
+
+    /// Iterates over the buffer from the oldest to the newest data
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::RingBuffer;
+    ///
+    /// let mut ring_buffer = RingBuffer::with_capacity(2);
+    /// ring_buffer.push(1);
+    /// ring_buffer.push(2);
+    /// ring_buffer.push(3);
+    /// assert_eq!(ring_buffer.iter().collect::<Vec<_>>(), vec![&2, &3]);
+    /// ```
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.buffer.iter()
+    }
+}
+
+/// Gets data by its age, where `0` is the oldest piece of data in the buffer
+/// ```rust
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_capacity(2);
+/// ring_buffer.push(1);
+/// ring_buffer.push(2);
+/// ring_buffer.push(3);
+/// assert_eq!(ring_buffer[0], 2);
+/// assert_eq!(ring_buffer[1], 3);
+/// ```
+impl<T, A> Index<usize> for RingBuffer<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.buffer[index]
+    }
 }
 
-impl<T: PartialEq> RingBuffer<T> {
+/// Pushes each item onto the buffer in turn, so only the newest items are kept
+/// ```rust
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_capacity(3);
+/// ring_buffer.extend(1..=5);
+/// assert_eq!(ring_buffer.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+/// ```
+impl<T, A: Aggregate<T>> Extend<T> for RingBuffer<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Collects into a buffer whose capacity is the number of items collected. To keep only the last
+/// few items, create the buffer `with_capacity` and `extend` it instead.
+/// ```rust
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let ring_buffer: RingBuffer<_> = (1..=3).collect();
+/// assert_eq!(ring_buffer.capacity(), 3);
+/// assert_eq!(ring_buffer.is_full(), true);
+/// ```
+impl<T> FromIterator<T> for RingBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let buffer: VecDeque<T> = iter.into_iter().collect();
+        RingBuffer {
+            capacity: buffer.len(),
+            buffer,
+            aggregate: (),
+        }
+    }
+}
+
+/// Consumes the buffer, returning data from the oldest to the newest
+/// ```rust
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_capacity(2);
+/// ring_buffer.extend(vec![1, 2, 3]);
+/// assert_eq!(ring_buffer.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+/// ```
+impl<T, A> IntoIterator for RingBuffer<T, A> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buffer.into_iter()
+    }
+}
+
+/// ```rust
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_capacity(2);
+/// ring_buffer.extend(vec![1, 2, 3]);
+/// let mut total = 0;
+/// for value in &ring_buffer {
+///     total += value;
+/// }
+/// assert_eq!(total, 5);
+/// ```
+impl<'a, T, A> IntoIterator for &'a RingBuffer<T, A> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buffer.iter()
+    }
+}
+
+impl<T: PartialEq, A> RingBuffer<T, A> {
     /// Checks to see if there are any duplicated in the ring
     /// ```rust
     /// use advent_of_code_2022::data_structure::RingBuffer;
@@ -131,8 +361,11 @@ impl<T: PartialEq> RingBuffer<T> {
     }
 }
 
-impl<T: Add<Output = T> + Default + Copy> RingBuffer<T> {
-    /// For data types that can be added together, this will get the total value of the buffer
+impl<T: Add<Output = T> + Default + Copy, A> RingBuffer<T, A> {
+    /// For data types that can be added together, this will get the total value of the buffer.
+    ///
+    /// Note: This adds up the whole buffer each time it's called. Use the
+    /// [`Sum`](aggregate::Sum) aggregate to keep a running total instead.
     /// ```rust
     /// use advent_of_code_2022::data_structure::RingBuffer;
     ///
@@ -0,0 +1,285 @@
+//! Running aggregates for a [`RingBuffer`](super::RingBuffer). Each one is updated as data is
+//! pushed onto the buffer and as old data falls off the other end, so reading the aggregate never
+//! has to look at the whole buffer.
+
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// Something that keeps track of a value derived from the data in a ring buffer.
+///
+/// Data always leaves in the order it arrived, so `evict` is always called with the oldest value
+/// that has been pushed and not yet evicted.
+pub trait Aggregate<T> {
+    type Output;
+
+    /// Called when a value is added to the buffer
+    fn push(&mut self, value: &T);
+
+    /// Called when the oldest value leaves the buffer
+    fn evict(&mut self, value: &T);
+
+    /// Called when the buffer is emptied
+    fn clear(&mut self);
+
+    /// Gets the current value of the aggregate
+    fn value(&self) -> Self::Output;
+}
+
+/// No aggregate at all. This is what a plain `RingBuffer` uses.
+impl<T> Aggregate<T> for () {
+    type Output = ();
+
+    fn push(&mut self, _value: &T) {}
+
+    fn evict(&mut self, _value: &T) {}
+
+    fn clear(&mut self) {}
+
+    fn value(&self) -> Self::Output {}
+}
+
+/// Keeps track of two aggregates at once
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::aggregate::{Max, Min};
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_aggregate(3, (Min::default(), Max::default()));
+/// ring_buffer.extend(vec![5, 1, 9, 4]);
+/// assert_eq!(ring_buffer.aggregate(), (Some(1), Some(9)));
+/// ```
+impl<T, A: Aggregate<T>, B: Aggregate<T>> Aggregate<T> for (A, B) {
+    type Output = (A::Output, B::Output);
+
+    fn push(&mut self, value: &T) {
+        self.0.push(value);
+        self.1.push(value);
+    }
+
+    fn evict(&mut self, value: &T) {
+        self.0.evict(value);
+        self.1.evict(value);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+        self.1.clear();
+    }
+
+    fn value(&self) -> Self::Output {
+        (self.0.value(), self.1.value())
+    }
+}
+
+/// Combines the values in the buffer, oldest first, with any associative operation that has an
+/// identity value (a monoid). The operation does not need to be reversible or commutative.
+///
+/// Values are kept on two stacks. New values go on the back stack, which tracks a running
+/// combination. When a value needs evicting and the front stack is empty, the back stack is moved
+/// across, storing the combination of each value with everything newer than it in the front
+/// stack. Each value is moved once, so this is O(1) amortised.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::aggregate::Monoid;
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// // Joins the last three strings in order
+/// let join = Monoid::new(String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+/// let mut ring_buffer = RingBuffer::with_aggregate(3, join);
+/// for s in ["a", "b", "c", "d"] {
+///     ring_buffer.push(s.to_string());
+/// }
+/// assert_eq!(ring_buffer.aggregate(), "bcd");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Monoid<T, F> {
+    identity: T,
+    combine: F,
+    front: Vec<T>,
+    back: Vec<T>,
+    back_total: T,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> Monoid<T, F> {
+    /// Creates an aggregate from an identity value and a way to combine two values
+    pub fn new(identity: T, combine: F) -> Self {
+        Monoid {
+            back_total: identity.clone(),
+            identity,
+            combine,
+            front: Vec::new(),
+            back: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> Aggregate<T> for Monoid<T, F> {
+    type Output = T;
+
+    fn push(&mut self, value: &T) {
+        self.back_total = (self.combine)(&self.back_total, value);
+        self.back.push(value.clone());
+    }
+
+    fn evict(&mut self, _value: &T) {
+        if self.front.is_empty() {
+            let mut total = self.identity.clone();
+            while let Some(value) = self.back.pop() {
+                total = (self.combine)(&value, &total);
+                self.front.push(total.clone());
+            }
+            self.back_total = self.identity.clone();
+        }
+        self.front.pop();
+    }
+
+    fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_total = self.identity.clone();
+    }
+
+    fn value(&self) -> Self::Output {
+        let front_total = self.front.last().unwrap_or(&self.identity);
+        (self.combine)(front_total, &self.back_total)
+    }
+}
+
+/// The total of the values in the buffer. Addition can be undone, so this keeps a running total
+/// and takes away each value as it leaves
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::aggregate::Sum;
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_aggregate(2, Sum::default());
+/// assert_eq!(ring_buffer.aggregate(), 0); // [] = 0
+/// ring_buffer.push(1);
+/// assert_eq!(ring_buffer.aggregate(), 1); // [1] = 1
+/// ring_buffer.push(2);
+/// assert_eq!(ring_buffer.aggregate(), 3); // [1, 2] = 3
+/// ring_buffer.push(3);
+/// assert_eq!(ring_buffer.aggregate(), 5); // [2, 3] = 5
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sum<T>(T);
+
+impl<T: Add<Output = T> + Sub<Output = T> + Default + Copy> Aggregate<T> for Sum<T> {
+    type Output = T;
+
+    fn push(&mut self, value: &T) {
+        self.0 = self.0 + *value;
+    }
+
+    fn evict(&mut self, value: &T) {
+        self.0 = self.0 - *value;
+    }
+
+    fn clear(&mut self) {
+        self.0 = T::default();
+    }
+
+    fn value(&self) -> Self::Output {
+        self.0
+    }
+}
+
+/// The smallest value in the buffer.
+///
+/// Keeps a queue of values that could still become the smallest, in increasing order. A new value
+/// removes any larger values from the back of the queue, as they will leave the buffer before it
+/// does. This is O(1) amortised.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::aggregate::Min;
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_aggregate(3, Min::default());
+/// assert_eq!(ring_buffer.aggregate(), None);
+/// ring_buffer.extend(vec![3, 1, 2]);
+/// assert_eq!(ring_buffer.aggregate(), Some(1)); // [3, 1, 2]
+/// ring_buffer.push(4);
+/// assert_eq!(ring_buffer.aggregate(), Some(1)); // [1, 2, 4]
+/// ring_buffer.push(5);
+/// assert_eq!(ring_buffer.aggregate(), Some(2)); // [2, 4, 5]
+/// ```
+#[derive(Debug, Clone)]
+pub struct Min<T>(VecDeque<T>);
+
+impl<T> Default for Min<T> {
+    fn default() -> Self {
+        Min(VecDeque::new())
+    }
+}
+
+impl<T: PartialOrd + Copy> Aggregate<T> for Min<T> {
+    type Output = Option<T>;
+
+    fn push(&mut self, value: &T) {
+        while self.0.back().is_some_and(|back| back > value) {
+            self.0.pop_back();
+        }
+        self.0.push_back(*value);
+    }
+
+    fn evict(&mut self, value: &T) {
+        if self.0.front() == Some(value) {
+            self.0.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn value(&self) -> Self::Output {
+        self.0.front().copied()
+    }
+}
+
+/// The largest value in the buffer. Works the same way as [`Min`].
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::aggregate::Max;
+/// use advent_of_code_2022::data_structure::RingBuffer;
+///
+/// let mut ring_buffer = RingBuffer::with_aggregate(3, Max::default());
+/// assert_eq!(ring_buffer.aggregate(), None);
+/// ring_buffer.extend(vec![3, 1, 2]);
+/// assert_eq!(ring_buffer.aggregate(), Some(3)); // [3, 1, 2]
+/// ring_buffer.push(1);
+/// assert_eq!(ring_buffer.aggregate(), Some(2)); // [1, 2, 1]
+/// ```
+#[derive(Debug, Clone)]
+pub struct Max<T>(VecDeque<T>);
+
+impl<T> Default for Max<T> {
+    fn default() -> Self {
+        Max(VecDeque::new())
+    }
+}
+
+impl<T: PartialOrd + Copy> Aggregate<T> for Max<T> {
+    type Output = Option<T>;
+
+    fn push(&mut self, value: &T) {
+        while self.0.back().is_some_and(|back| back < value) {
+            self.0.pop_back();
+        }
+        self.0.push_back(*value);
+    }
+
+    fn evict(&mut self, value: &T) {
+        if self.0.front() == Some(value) {
+            self.0.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    fn value(&self) -> Self::Output {
+        self.0.front().copied()
+    }
+}
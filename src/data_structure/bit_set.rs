@@ -0,0 +1,321 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitOr, Sub};
+
+/// A set of numbers from 0 to 63 stored as the bits of a single `u64`. Union, intersection and
+/// counting are each a single instruction.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::BitSet64;
+///
+/// let a: BitSet64 = [1, 3, 5].into_iter().collect();
+/// let b: BitSet64 = [3, 4, 5].into_iter().collect();
+/// assert_eq!(a.intersection(b).iter().collect::<Vec<_>>(), vec![3, 5]);
+/// assert_eq!((a | b).len(), 4);
+/// assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1]);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitSet64(u64);
+
+impl BitSet64 {
+    /// The largest number of values the set can hold
+    pub const CAPACITY: usize = 64;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The set as a `u64`, where bit `n` is set if `n` is in the set
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Adds a value to the set, returning false if it was already there. Panics if the value is
+    /// 64 or more
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "{} is too large for a BitSet64",
+            value
+        );
+        let was_present = self.contains(value);
+        self.0 |= 1 << value;
+        !was_present
+    }
+
+    /// Takes a value out of the set, returning false if it wasn't there
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.0 &= !(1 << value);
+        }
+        was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.0 & (1 << value) != 0
+    }
+
+    /// The number of values in the set
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        BitSet64(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        BitSet64(self.0 & other.0)
+    }
+
+    /// The values in this set that aren't in the other
+    pub fn difference(self, other: Self) -> Self {
+        BitSet64(self.0 & !other.0)
+    }
+
+    /// Iterates over the values in the set from smallest to largest
+    pub fn iter(&self) -> Bits {
+        Bits(self.0)
+    }
+}
+
+impl From<u64> for BitSet64 {
+    fn from(bits: u64) -> Self {
+        BitSet64(bits)
+    }
+}
+
+impl BitOr for BitSet64 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for BitSet64 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for BitSet64 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl Debug for BitSet64 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet64 {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet64 {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl IntoIterator for BitSet64 {
+    type Item = usize;
+    type IntoIter = Bits;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over the set bits of a `u64`, lowest first
+#[derive(Debug, Clone)]
+pub struct Bits(u64);
+
+impl Iterator for Bits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros() as usize;
+        // Clears the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+/// A set of numbers stored as bits, that grows to fit the largest number in it
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::BitSet;
+///
+/// let mut set = BitSet::new();
+/// set.insert(3);
+/// set.insert(200);
+/// assert!(set.contains(200));
+/// assert!(!set.contains(1000));
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 200]);
+///
+/// let other: BitSet = [3, 4].into_iter().collect();
+/// assert_eq!(set.union(&other).len(), 3);
+/// assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), vec![3]);
+/// ```
+#[derive(Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to the set, returning false if it was already there
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, value % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let was_present = self.words[word] & (1 << bit) != 0;
+        self.words[word] |= 1 << bit;
+        !was_present
+    }
+
+    /// Takes a value out of the set, returning false if it wasn't there
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_present = self.contains(value);
+        if was_present {
+            self.words[value / 64] &= !(1 << (value % 64));
+        }
+        was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / 64)
+            .is_some_and(|word| word & (1 << (value % 64)) != 0)
+    }
+
+    /// The number of values in the set
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+        words
+            .iter_mut()
+            .zip(&shorter.words)
+            .for_each(|(a, b)| *a |= b);
+        BitSet { words }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect();
+        BitSet { words }
+    }
+
+    /// The values in this set that aren't in the other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut words = self.words.clone();
+        words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= !b);
+        BitSet { words }
+    }
+
+    /// Iterates over the values in the set from smallest to largest
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, word)| Bits(*word).map(move |bit| i * 64 + bit))
+    }
+}
+
+/// Sets are equal if they hold the same values, however much space they've grown to
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        longer.words[..shorter.words.len()] == shorter.words[..]
+            && longer.words[shorter.words.len()..].iter().all(|w| *w == 0)
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<BitSet64> for BitSet {
+    fn from(set: BitSet64) -> Self {
+        BitSet {
+            words: vec![set.bits()],
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+/// A list where the end wraps around to the start, built for moving elements around many times.
+///
+/// Every element keeps the id it was given when the list was created (its position in the
+/// original order), no matter where it moves to. Methods that take an id return `None` if there's
+/// no element with that id, rather than panicking.
+///
+/// The order is kept in blocks of about √n ids. Finding an element's position or moving it only
+/// means looking through one block and adding up the lengths of the blocks before it, so each move
+/// is O(√n) rather than the O(n) of removing and inserting in a single `Vec`.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::CircularList;
+///
+/// let numbers = vec![1, 2, -3, 3, -2, 0, 4];
+/// let mut list: CircularList<i64> = numbers.iter().copied().collect();
+/// for (id, value) in numbers.iter().enumerate() {
+///     list.move_by(id, *value);
+/// }
+/// let mixed: Vec<_> = list.iter_from(0).unwrap().copied().collect();
+/// assert_eq!(mixed, vec![1, 2, -3, 4, 0, 3, -2]);
+///
+/// let coordinates: i64 = [1000, 2000, 3000]
+///     .into_iter()
+///     .map(|n| list.nth_after_value(&0, n).unwrap())
+///     .sum();
+/// assert_eq!(coordinates, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircularList<T> {
+    values: Vec<T>,
+    blocks: Vec<Vec<usize>>,
+    block_of: Vec<usize>,
+    block_size: usize,
+    /// Moves since the blocks were last split evenly
+    moves: usize,
+}
+
+impl<T> CircularList<T> {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Gets the value of an element by its id
+    pub fn get(&self, id: usize) -> Option<&T> {
+        self.values.get(id)
+    }
+
+    /// Splits the order back into evenly sized blocks
+    fn rebuild(&mut self) {
+        self.moves = 0;
+        let order: Vec<usize> = self.blocks.iter().flatten().copied().collect();
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        for (block, ids) in self.blocks.iter().enumerate() {
+            for id in ids {
+                self.block_of[*id] = block;
+            }
+        }
+    }
+
+    /// How far round the list an element is, counting from the start of the first block
+    fn index_of(&self, id: usize) -> usize {
+        let block = self.block_of[id];
+        let before: usize = self.blocks[..block].iter().map(Vec::len).sum();
+        let within = self.blocks[block]
+            .iter()
+            .position(|other| *other == id)
+            .expect("Element missing from its block");
+        before + within
+    }
+
+    /// Finds the block an index falls in and the position within that block
+    fn locate(&self, mut index: usize) -> (usize, usize) {
+        for (block, ids) in self.blocks.iter().enumerate() {
+            if index < ids.len() {
+                return (block, index);
+            }
+            index -= ids.len();
+        }
+        // Only reachable when inserting at the very end
+        let last = self.blocks.len() - 1;
+        (last, self.blocks[last].len())
+    }
+
+    fn id_at(&self, index: usize) -> usize {
+        let (block, within) = self.locate(index);
+        self.blocks[block][within]
+    }
+
+    /// Moves an element `offset` places forwards (or backwards if negative). The element is taken
+    /// out before moving, so moving by `len - 1` places brings it back to where it started.
+    /// Returns `None` if there's no element with the id
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::CircularList;
+    ///
+    /// let mut list: CircularList<char> = "abcde".chars().collect();
+    /// list.move_by(0, 2);
+    /// assert_eq!(list.iter_from(0).unwrap().collect::<String>(), "adebc");
+    /// list.move_by(0, -1);
+    /// assert_eq!(list.iter_from(0).unwrap().collect::<String>(), "acdeb");
+    /// list.move_by(0, 4);
+    /// assert_eq!(list.iter_from(0).unwrap().collect::<String>(), "acdeb");
+    /// assert_eq!(list.move_by(5, 1), None);
+    /// ```
+    pub fn move_by(&mut self, id: usize, offset: i64) -> Option<()> {
+        let len = self.len();
+        if id >= len {
+            return None;
+        }
+        if len == 1 {
+            return Some(());
+        }
+        let from = self.index_of(id);
+        let to = (from as i64 + offset).rem_euclid(len as i64 - 1) as usize;
+        if from == to {
+            return Some(());
+        }
+
+        let block = self.block_of[id];
+        let within = from - self.blocks[..block].iter().map(Vec::len).sum::<usize>();
+        self.blocks[block].remove(within);
+        let emptied = self.blocks[block].is_empty();
+
+        let (block, within) = self.locate(to);
+        self.blocks[block].insert(within, id);
+        self.block_of[id] = block;
+        self.moves += 1;
+        // Uneven blocks make finding positions slower, so they're evened out every √n moves,
+        // which keeps the cost of rebuilding to O(√n) a move
+        if emptied
+            || self.blocks[block].len() > self.block_size * 2
+            || self.moves >= self.block_size
+        {
+            self.rebuild();
+        }
+        Some(())
+    }
+
+    /// Goes once round the list starting at an element. Returns `None` if there's no element with
+    /// the id
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::CircularList;
+    ///
+    /// let list: CircularList<char> = "abcd".chars().collect();
+    /// assert_eq!(list.iter_from(2).unwrap().collect::<String>(), "cdab");
+    /// assert!(list.iter_from(4).is_none());
+    /// ```
+    pub fn iter_from(&self, id: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if id >= self.len() {
+            return None;
+        }
+        let start = self.index_of(id);
+        Some(
+            self.blocks
+                .iter()
+                .flatten()
+                .cycle()
+                .skip(start)
+                .take(self.len())
+                .map(|id| &self.values[*id]),
+        )
+    }
+
+    /// The id of the element `n` places after another, going round as many times as needed.
+    /// Returns `None` if there's no element with the id
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::CircularList;
+    ///
+    /// let list: CircularList<char> = "abc".chars().collect();
+    /// assert_eq!(list.nth_after(0, 4), Some(1));
+    /// assert_eq!(list.nth_after(3, 1), None);
+    ///
+    /// let empty: CircularList<char> = "".chars().collect();
+    /// assert_eq!(empty.nth_after(0, 1), None);
+    /// ```
+    pub fn nth_after(&self, id: usize, n: usize) -> Option<usize> {
+        if id >= self.len() {
+            return None;
+        }
+        Some(self.id_at((self.index_of(id) + n) % self.len()))
+    }
+}
+
+impl<T: PartialEq> CircularList<T> {
+    /// Finds the id of the first element, in the original order, with a value
+    pub fn find(&self, value: &T) -> Option<usize> {
+        self.values.iter().position(|other| other == value)
+    }
+
+    /// Gets the value `n` places after the first element with a value
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::CircularList;
+    ///
+    /// let list: CircularList<i32> = vec![5, 0, 7].into_iter().collect();
+    /// assert_eq!(list.nth_after_value(&0, 1), Some(&7));
+    /// assert_eq!(list.nth_after_value(&0, 5), Some(&5));
+    /// assert_eq!(list.nth_after_value(&9, 1), None);
+    /// ```
+    pub fn nth_after_value(&self, value: &T, n: usize) -> Option<&T> {
+        let id = self.find(value)?;
+        self.get(self.nth_after(id, n)?)
+    }
+}
+
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        let len = values.len();
+        let mut list = CircularList {
+            values,
+            blocks: vec![(0..len).collect()],
+            block_of: vec![0; len],
+            block_size: ((len as f64).sqrt() as usize).max(1),
+            moves: 0,
+        };
+        list.rebuild();
+        if list.blocks.is_empty() {
+            list.blocks.push(Vec::new());
+        }
+        list
+    }
+}
@@ -0,0 +1,103 @@
+//! Finds where a simulation starts repeating itself, so its result after a huge number of steps
+//! can be worked out without running every step.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A repeating section of a simulation, along with a counter recorded at every step up to the end
+/// of the first repeat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step that is part of the cycle
+    pub start: usize,
+    /// How many steps it takes to get back to the same state
+    pub length: usize,
+    /// The counter at each step from 0 to `start + length`
+    counters: Vec<i64>,
+}
+
+impl Cycle {
+    /// Finds the earliest step that is in the same state as a step
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::cycle::find_cycle;
+    ///
+    /// // 0, 1, 2, 3, 4, 2, 3, 4, 2, ...
+    /// let cycle = find_cycle(0, |n| *n = if *n == 4 { 2 } else { *n + 1 }, |n| *n, |_| 0, 100).unwrap();
+    /// assert_eq!((cycle.start, cycle.length), (2, 3));
+    /// assert_eq!(cycle.equivalent_step(1), 1);
+    /// assert_eq!(cycle.equivalent_step(10), 4);
+    /// ```
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+
+    /// Works out what the counter would be at any step, assuming it goes up by the same amount
+    /// each time round the cycle
+    pub fn counter_at(&self, step: usize) -> i64 {
+        if step < self.counters.len() {
+            return self.counters[step];
+        }
+        let cycles = ((step - self.start) / self.length) as i64;
+        let per_cycle = self.counters[self.start + self.length] - self.counters[self.start];
+        self.counters[self.equivalent_step(step)] + per_cycle * cycles
+    }
+}
+
+/// Runs a simulation until a state repeats, or until `limit` steps have been taken.
+///
+/// - `step` moves the state on by one step
+/// - `fingerprint` gives a key for a state. Two states with the same key must carry on in exactly
+///   the same way. It can leave out anything that only affects the counter
+/// - `counter` reads a number from a state (such as the height of a tower) that can then be
+///   worked out for any step with [`Cycle::counter_at`]
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::cycle::find_cycle;
+///
+/// // A position that moves around a small loop, and the total of every position visited
+/// let step = |(position, total): &mut (i64, i64)| {
+///     *position = (*position * 3 + 1) % 10;
+///     *total += *position;
+/// };
+/// let cycle = find_cycle((0, 0), step, |(position, _)| *position, |(_, total)| *total, 1000)
+///     .unwrap();
+///
+/// let mut brute_force = (0, 0);
+/// for _ in 0..1_000_000 {
+///     step(&mut brute_force);
+/// }
+/// assert_eq!(cycle.counter_at(1_000_000), brute_force.1);
+/// ```
+pub fn find_cycle<S, K, F, P, C>(
+    mut state: S,
+    mut step: F,
+    mut fingerprint: P,
+    mut counter: C,
+    limit: usize,
+) -> Option<Cycle>
+where
+    K: Hash + Eq,
+    F: FnMut(&mut S),
+    P: FnMut(&S) -> K,
+    C: FnMut(&S) -> i64,
+{
+    let mut seen = HashMap::new();
+    let mut counters = Vec::new();
+    for index in 0..=limit {
+        counters.push(counter(&state));
+        if let Some(start) = seen.insert(fingerprint(&state), index) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+                counters,
+            });
+        }
+        step(&mut state);
+    }
+    None
+}
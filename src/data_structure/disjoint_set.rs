@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Splits the ids `0..len` into groups (components) that can be joined together, and quickly
+/// answers which group an id is in.
+///
+/// Each component is a tree of ids pointing towards a root. Looking up a root points every id on
+/// the way directly at it (path compression), and joining two components hangs the shallower tree
+/// off the deeper one (union by rank), so operations are close to O(1).
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::DisjointSet;
+///
+/// let mut set = DisjointSet::new(5);
+/// set.union(0, 1);
+/// set.union(3, 4);
+/// set.union(1, 4);
+/// assert!(set.same(0, 3));
+/// assert!(!set.same(0, 2));
+/// assert_eq!(set.component_count(), 2);
+/// assert_eq!(set.size_of(4), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Creates a set where each of the ids `0..len` is in its own component
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// The number of ids
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new id in a component of its own, returning the id
+    pub fn add(&mut self) -> usize {
+        let id = self.len();
+        self.parent.push(id);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        id
+    }
+
+    /// Finds the id that represents the component an id is in
+    pub fn find(&mut self, id: usize) -> usize {
+        let mut root = id;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = id;
+        while current != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Joins the components two ids are in. Returns false if they were already in the same
+    /// component
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (parent, child) = if self.rank[a] >= self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        if self.rank[parent] == self.rank[child] {
+            self.rank[parent] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    /// Checks if two ids are in the same component
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of ids in the same component as an id, including itself
+    pub fn size_of(&mut self, id: usize) -> usize {
+        let root = self.find(id);
+        self.size[root]
+    }
+
+    /// The number of separate components
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Lists every component, each as its ids in increasing order. Components are ordered by
+    /// their smallest id
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::DisjointSet;
+    ///
+    /// let mut set = DisjointSet::new(5);
+    /// set.union(4, 1);
+    /// set.union(2, 0);
+    /// assert_eq!(set.components(), vec![vec![0, 2], vec![1, 4], vec![3]]);
+    /// ```
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut by_root: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for id in 0..self.len() {
+            let root = self.find(id);
+            let index = *by_root.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[index].push(id);
+        }
+        components
+    }
+}
+
+/// A [`DisjointSet`] of any hashable keys rather than ids. Keys are added the first time they're
+/// seen.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Point3;
+/// use advent_of_code_2022::data_structure::KeyedDisjointSet;
+///
+/// // Join cubes that share a face
+/// let cubes = [(1, 1, 1), (2, 1, 1), (5, 5, 5), (5, 5, 6), (5, 6, 6)].map(Point3::from);
+/// let mut set = KeyedDisjointSet::new();
+/// for a in cubes {
+///     set.insert(a);
+///     for b in cubes {
+///         if a.manhattan(b) == 1 {
+///             set.union(a, b);
+///         }
+///     }
+/// }
+/// assert_eq!(set.component_count(), 2);
+/// assert_eq!(set.size_of(&Point3::new(5, 6, 6)), Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+    set: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        KeyedDisjointSet {
+            ids: HashMap::new(),
+            keys: Vec::new(),
+            set: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.ids.contains_key(key)
+    }
+
+    /// Adds a key in a component of its own if it hasn't been seen before, returning its id
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+        let id = self.set.add();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    /// Joins the components two keys are in, adding either key if needed. Returns false if they
+    /// were already in the same component
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.set.union(a, b)
+    }
+
+    /// Finds the key that represents the component a key is in
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let id = *self.ids.get(key)?;
+        let root = self.set.find(id);
+        Some(&self.keys[root])
+    }
+
+    /// Checks if two keys are in the same component. Keys that haven't been added aren't in any
+    /// component
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.ids.get(a), self.ids.get(b)) {
+            (Some(a), Some(b)) => self.set.same(*a, *b),
+            _ => false,
+        }
+    }
+
+    /// The number of keys in the same component as a key, including itself
+    pub fn size_of(&mut self, key: &K) -> Option<usize> {
+        let id = *self.ids.get(key)?;
+        Some(self.set.size_of(id))
+    }
+
+    /// The number of separate components
+    pub fn component_count(&self) -> usize {
+        self.set.component_count()
+    }
+
+    /// Lists every component, each as its keys in the order they were added
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::KeyedDisjointSet;
+    ///
+    /// let mut set = KeyedDisjointSet::new();
+    /// set.union("a", "c");
+    /// set.insert("b");
+    /// set.union("d", "c");
+    /// assert_eq!(set.components(), vec![vec!["a", "c", "d"], vec!["b"]]);
+    /// ```
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        self.set
+            .components()
+            .into_iter()
+            .map(|ids| ids.into_iter().map(|id| self.keys[id].clone()).collect())
+            .collect()
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::{HashMap, VecDeque};
+
+/// A sliding window of symbols (such as `u8` or `char`) that knows whether all of its symbols are
+/// different.
+///
+/// Rather than comparing every pair of symbols, it counts how many of each symbol are in the
+/// window and how many symbols are duplicates, updating both as symbols enter and leave. This makes
+/// each push O(1) no matter how big the window is.
+///
+/// Counts for symbols below 256 are kept in a fixed table, so bytes and ASCII never allocate.
+/// Wider symbols are counted in a map that only holds what is currently in the window.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::DistinctWindow;
+///
+/// let mut window = DistinctWindow::with_capacity(2);
+/// window.push('\u{10FFFF}');
+/// window.push('a');
+/// assert!(window.is_distinct());
+/// window.push('a');
+/// assert_eq!(window.duplicates(), 1);
+/// ```
+pub struct DistinctWindow<T> {
+    window: VecDeque<T>,
+    capacity: usize,
+    counts: Counts,
+    duplicates: usize,
+}
+
+/// How many of each symbol are in a window
+struct Counts {
+    narrow: [usize; 256],
+    wide: HashMap<u32, usize>,
+}
+
+impl Counts {
+    fn new() -> Self {
+        Counts {
+            narrow: [0; 256],
+            wide: HashMap::new(),
+        }
+    }
+
+    /// Counts one more of a symbol, returning the new count
+    fn increment(&mut self, symbol: u32) -> usize {
+        let count = match self.narrow.get_mut(symbol as usize) {
+            Some(count) => count,
+            None => self.wide.entry(symbol).or_insert(0),
+        };
+        *count += 1;
+        *count
+    }
+
+    /// Counts one less of a symbol, returning the new count
+    fn decrement(&mut self, symbol: u32) -> usize {
+        if let Some(count) = self.narrow.get_mut(symbol as usize) {
+            *count -= 1;
+            return *count;
+        }
+        let count = self
+            .wide
+            .get_mut(&symbol)
+            .expect("Symbol left the window without entering it");
+        *count -= 1;
+        let remaining = *count;
+        // Keeps the map no bigger than the window
+        if remaining == 0 {
+            self.wide.remove(&symbol);
+        }
+        remaining
+    }
+}
+
+impl<T: Copy + Into<u32>> DistinctWindow<T> {
+    /// Creates a window that holds up to `capacity` symbols
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::DistinctWindow;
+    ///
+    /// let window = DistinctWindow::<char>::with_capacity(4);
+    /// assert_eq!(window.capacity(), 4);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        DistinctWindow {
+            window: VecDeque::with_capacity(capacity),
+            capacity,
+            counts: Counts::new(),
+            duplicates: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Adds a symbol to the window, removing the oldest symbol if the window is full
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::DistinctWindow;
+    ///
+    /// let mut window = DistinctWindow::with_capacity(3);
+    /// window.push('a');
+    /// window.push('b');
+    /// window.push('a');
+    /// assert_eq!(window.duplicates(), 1);
+    /// window.push('c'); // The first 'a' leaves the window
+    /// assert_eq!(window.duplicates(), 0);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.is_full() {
+            let oldest = self.window.pop_front().expect("Full window was empty");
+            if self.counts.decrement(oldest.into()) > 0 {
+                self.duplicates -= 1;
+            }
+        }
+        if self.counts.increment(value.into()) > 1 {
+            self.duplicates += 1;
+        }
+        self.window.push_back(value);
+    }
+
+    /// The number of symbols in the window that are a repeat of an earlier symbol in the window
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Checks to see if the window is full and every symbol in it is different
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::DistinctWindow;
+    ///
+    /// let mut window = DistinctWindow::with_capacity(2);
+    /// window.push(b'a');
+    /// assert_eq!(window.is_distinct(), false); // Not full yet
+    /// window.push(b'b');
+    /// assert_eq!(window.is_distinct(), true);
+    /// window.push(b'b');
+    /// assert_eq!(window.is_distinct(), false);
+    /// ```
+    pub fn is_distinct(&self) -> bool {
+        self.is_full() && self.duplicates == 0
+    }
+}
+
+/// Finds the first point in a sequence where the previous `size` symbols were all different,
+/// returning how many symbols had been read at that point
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::first_distinct_window;
+///
+/// assert_eq!(first_distinct_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(), 4), Some(7));
+/// assert_eq!(first_distinct_window("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars(), 14), Some(19));
+/// assert_eq!(first_distinct_window("aaaa".chars(), 2), None);
+/// ```
+pub fn first_distinct_window<T, I>(iter: I, size: usize) -> Option<usize>
+where
+    T: Copy + Into<u32>,
+    I: IntoIterator<Item = T>,
+{
+    let mut window = DistinctWindow::with_capacity(size);
+    for (i, value) in iter.into_iter().enumerate() {
+        window.push(value);
+        if window.is_distinct() {
+            return Some(i + 1);
+        }
+    }
+    None
+}
@@ -0,0 +1,591 @@
+//! Arithmetic expressions stored as a graph, which can be evaluated, simplified, and solved for a
+//! single unknown.
+//!
+//! ```rust
+//! use advent_of_code_2022::data_structure::expr::{Expr, ExprArena, Op};
+//! use std::collections::HashMap;
+//!
+//! let monkeys = [
+//!     "root: pppw + sjmn", "dbpl: 5", "cczh: sllz + lgvd", "zczc: 2", "ptdq: humn - dvpt",
+//!     "dvpt: 3", "lfqf: 4", "humn: 5", "ljgn: 2", "sjmn: drzm * dbpl", "sllz: 4",
+//!     "pppw: cczh / lfqf", "lgvd: ljgn * ptdq", "drzm: hmdt - zczc", "hmdt: 32",
+//! ];
+//!
+//! // Monkeys can refer to monkeys further down the list, so give every name an id first
+//! let mut arena = ExprArena::<i128>::new();
+//! let ids: HashMap<&str, _> = monkeys.iter().map(|m| (&m[..4], arena.declare())).collect();
+//! for monkey in monkeys {
+//!     let (name, job) = monkey.split_once(": ").unwrap();
+//!     let expr = match job.split(' ').collect::<Vec<_>>()[..] {
+//!         [a, op, b] => Expr::Op(Op::try_from(op).unwrap(), ids[a], ids[b]),
+//!         [n] => Expr::Constant(n.parse().unwrap()),
+//!         _ => panic!("Unexpected job {}", job),
+//!     };
+//!     arena.define(ids[name], expr);
+//! }
+//! assert_eq!(arena.evaluate(ids["root"], None), Ok(152));
+//!
+//! // In part 2 the human is the unknown, and the two sides of root must be equal
+//! arena.define(ids["humn"], Expr::Unknown);
+//! let (lhs, rhs) = match arena.get(ids["root"]) {
+//!     Expr::Op(_, lhs, rhs) => (*lhs, *rhs),
+//!     _ => panic!("Root should be an operation"),
+//! };
+//! assert_eq!(arena.solve(lhs, rhs), Ok(301));
+//! ```
+
+use std::fmt::{Debug, Display, Formatter};
+
+/// Something wrong with an expression, or a reason it couldn't be evaluated or solved
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExprError {
+    /// A number got too big for the number type
+    Overflow,
+    DivisionByZero,
+    /// Integer division that would leave a remainder
+    InexactDivision,
+    /// An expression was declared but never defined
+    Undefined(ExprId),
+    /// An expression depends on itself
+    Cycle(ExprId),
+    /// The unknown was needed, but no value was given for it
+    MissingUnknown,
+    /// Solving needs the unknown on exactly one side of the equation
+    NoUnknown,
+    /// Both sides of an operation depend on the unknown, so it can't be undone
+    NonLinear(ExprId),
+    /// Every value of the unknown solves the equation
+    Ambiguous,
+    /// No value of the unknown solves the equation
+    NoSolution,
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExprError::Overflow => write!(f, "Arithmetic overflow"),
+            ExprError::DivisionByZero => write!(f, "Division by zero"),
+            ExprError::InexactDivision => write!(f, "Division leaves a remainder"),
+            ExprError::Undefined(id) => write!(f, "Expression {} is not defined", id.0),
+            ExprError::Cycle(id) => write!(f, "Expression {} depends on itself", id.0),
+            ExprError::MissingUnknown => write!(f, "No value given for the unknown"),
+            ExprError::NoUnknown => write!(f, "Equation does not contain the unknown"),
+            ExprError::NonLinear(id) => {
+                write!(f, "Both sides of expression {} depend on the unknown", id.0)
+            }
+            ExprError::Ambiguous => write!(f, "Every value of the unknown is a solution"),
+            ExprError::NoSolution => write!(f, "No value of the unknown is a solution"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Numbers that expressions can be evaluated with. Every operation is checked.
+pub trait Number: Copy + PartialEq + Debug + Display {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Result<Self, ExprError>;
+    fn sub(self, other: Self) -> Result<Self, ExprError>;
+    fn mul(self, other: Self) -> Result<Self, ExprError>;
+    fn div(self, other: Self) -> Result<Self, ExprError>;
+}
+
+/// Integer arithmetic, where division must not leave a remainder
+impl Number for i128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn add(self, other: Self) -> Result<Self, ExprError> {
+        self.checked_add(other).ok_or(ExprError::Overflow)
+    }
+
+    fn sub(self, other: Self) -> Result<Self, ExprError> {
+        self.checked_sub(other).ok_or(ExprError::Overflow)
+    }
+
+    fn mul(self, other: Self) -> Result<Self, ExprError> {
+        self.checked_mul(other).ok_or(ExprError::Overflow)
+    }
+
+    fn div(self, other: Self) -> Result<Self, ExprError> {
+        if other == 0 {
+            return Err(ExprError::DivisionByZero);
+        }
+        if self.checked_rem(other).ok_or(ExprError::Overflow)? != 0 {
+            return Err(ExprError::InexactDivision);
+        }
+        self.checked_div(other).ok_or(ExprError::Overflow)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// An exact fraction, always kept in its lowest terms with a positive denominator
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::expr::{Number, Rational};
+///
+/// let half = Rational::new(2, -4).unwrap();
+/// assert_eq!(half, Rational::new(-1, 2).unwrap());
+/// assert_eq!(half.to_string(), "-1/2");
+/// assert_eq!(half.mul(Rational::from(6)).unwrap().to_integer(), Some(-3));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    /// Creates a fraction, failing if the denominator is zero
+    pub fn new(numerator: i128, denominator: i128) -> Result<Self, ExprError> {
+        if denominator == 0 {
+            return Err(ExprError::DivisionByZero);
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Ok(Rational {
+            numerator: numerator.checked_div(divisor).ok_or(ExprError::Overflow)?,
+            denominator: denominator
+                .checked_div(divisor)
+                .ok_or(ExprError::Overflow)?,
+        })
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    /// The value as an integer, if it is one
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.to_integer() {
+            Some(n) => write!(f, "{}", n),
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+impl Number for Rational {
+    fn zero() -> Self {
+        Rational::from(0)
+    }
+
+    fn add(self, other: Self) -> Result<Self, ExprError> {
+        let numerator = self
+            .numerator
+            .mul(other.denominator)?
+            .add(other.numerator.mul(self.denominator)?)?;
+        Rational::new(numerator, self.denominator.mul(other.denominator)?)
+    }
+
+    fn sub(self, other: Self) -> Result<Self, ExprError> {
+        let negated = Rational {
+            numerator: 0.sub(other.numerator)?,
+            denominator: other.denominator,
+        };
+        self.add(negated)
+    }
+
+    fn mul(self, other: Self) -> Result<Self, ExprError> {
+        Rational::new(
+            self.numerator.mul(other.numerator)?,
+            self.denominator.mul(other.denominator)?,
+        )
+    }
+
+    fn div(self, other: Self) -> Result<Self, ExprError> {
+        Rational::new(
+            self.numerator.mul(other.denominator)?,
+            self.denominator.mul(other.numerator)?,
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    pub fn apply<N: Number>(&self, a: N, b: N) -> Result<N, ExprError> {
+        match self {
+            Op::Add => a.add(b),
+            Op::Sub => a.sub(b),
+            Op::Mul => a.mul(b),
+            Op::Div => a.div(b),
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+        }
+    }
+}
+
+/// Reads `+`, `-`, `*` or `/`
+impl TryFrom<&str> for Op {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "+" => Ok(Op::Add),
+            "-" => Ok(Op::Sub),
+            "*" => Ok(Op::Mul),
+            "/" => Ok(Op::Div),
+            _ => Err(format!("Invalid operator: {}", s)),
+        }
+    }
+}
+
+/// Refers to an expression in an [`ExprArena`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExprId(usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Expr<N> {
+    /// Declared, but not defined yet
+    Undefined,
+    Constant(N),
+    /// The value being solved for. Every `Unknown` is the same value
+    Unknown,
+    Op(Op, ExprId, ExprId),
+}
+
+/// Where an expression is up to during evaluation
+#[derive(Copy, Clone)]
+enum Visit<N> {
+    NotVisited,
+    InProgress,
+    Done(N),
+}
+
+/// Holds expressions that refer to each other by [`ExprId`]. Any expression can be used by many
+/// others, so shared parts are only stored and evaluated once.
+#[derive(Debug, Clone)]
+pub struct ExprArena<N> {
+    exprs: Vec<Expr<N>>,
+}
+
+impl<N> Default for ExprArena<N> {
+    fn default() -> Self {
+        ExprArena { exprs: Vec::new() }
+    }
+}
+
+impl<N: Number> ExprArena<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, expr: Expr<N>) -> ExprId {
+        self.exprs.push(expr);
+        ExprId(self.exprs.len() - 1)
+    }
+
+    pub fn constant(&mut self, n: N) -> ExprId {
+        self.add(Expr::Constant(n))
+    }
+
+    pub fn unknown(&mut self) -> ExprId {
+        self.add(Expr::Unknown)
+    }
+
+    pub fn op(&mut self, op: Op, a: ExprId, b: ExprId) -> ExprId {
+        self.add(Expr::Op(op, a, b))
+    }
+
+    /// Makes an id for an expression that will be defined later, so that other expressions can
+    /// refer to it first
+    pub fn declare(&mut self) -> ExprId {
+        self.add(Expr::Undefined)
+    }
+
+    /// Replaces an expression
+    pub fn define(&mut self, id: ExprId, expr: Expr<N>) {
+        self.exprs[id.0] = expr;
+    }
+
+    pub fn get(&self, id: ExprId) -> &Expr<N> {
+        &self.exprs[id.0]
+    }
+
+    /// Works out the value of every expression `id` depends on, in an order where each expression
+    /// comes after the ones it uses
+    fn evaluate_all(
+        &self,
+        id: ExprId,
+        unknown: Option<N>,
+        visits: &mut [Visit<Option<N>>],
+    ) -> Result<Option<N>, ExprError> {
+        // Uses its own stack, as real inputs can be deep enough to overflow the call stack
+        let mut stack = vec![id];
+        while let Some(&current) = stack.last() {
+            match visits[current.0] {
+                Visit::Done(_) => {
+                    stack.pop();
+                }
+                Visit::NotVisited => {
+                    visits[current.0] = Visit::InProgress;
+                    if let Expr::Op(_, a, b) = self.exprs[current.0] {
+                        for child in [b, a] {
+                            match visits[child.0] {
+                                Visit::InProgress => return Err(ExprError::Cycle(child)),
+                                Visit::NotVisited => stack.push(child),
+                                Visit::Done(_) => {}
+                            }
+                        }
+                    }
+                }
+                Visit::InProgress => {
+                    let value = match self.exprs[current.0] {
+                        Expr::Undefined => return Err(ExprError::Undefined(current)),
+                        Expr::Constant(n) => Some(n),
+                        Expr::Unknown => unknown,
+                        Expr::Op(op, a, b) => match (visits[a.0], visits[b.0]) {
+                            (Visit::Done(Some(a)), Visit::Done(Some(b))) => Some(op.apply(a, b)?),
+                            _ => None,
+                        },
+                    };
+                    visits[current.0] = Visit::Done(value);
+                    stack.pop();
+                }
+            }
+        }
+        match visits[id.0] {
+            Visit::Done(value) => Ok(value),
+            _ => unreachable!("Expression was not evaluated"),
+        }
+    }
+
+    /// Works out the value of an expression. If it depends on the unknown, a value for the unknown
+    /// must be given
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::expr::{ExprArena, ExprError, Op, Rational};
+    ///
+    /// let mut arena = ExprArena::<Rational>::new();
+    /// let one = arena.constant(Rational::from(1));
+    /// let three = arena.constant(Rational::from(3));
+    /// let third = arena.op(Op::Div, one, three);
+    /// assert_eq!(arena.evaluate(third, None), Ok(Rational::new(1, 3).unwrap()));
+    ///
+    /// let x = arena.unknown();
+    /// let sum = arena.op(Op::Add, third, x);
+    /// assert_eq!(arena.evaluate(sum, None), Err(ExprError::MissingUnknown));
+    /// assert_eq!(arena.evaluate(sum, Some(Rational::from(2))), Ok(Rational::new(7, 3).unwrap()));
+    ///
+    /// // The same thing with integers can't be worked out exactly
+    /// let mut arena = ExprArena::<i128>::new();
+    /// let one = arena.constant(1);
+    /// let three = arena.constant(3);
+    /// let third = arena.op(Op::Div, one, three);
+    /// assert_eq!(arena.evaluate(third, None), Err(ExprError::InexactDivision));
+    /// ```
+    pub fn evaluate(&self, id: ExprId, unknown: Option<N>) -> Result<N, ExprError> {
+        let mut visits = vec![Visit::NotVisited; self.exprs.len()];
+        self.evaluate_all(id, unknown, &mut visits)?
+            .ok_or(ExprError::MissingUnknown)
+    }
+
+    /// Checks if an expression uses the unknown anywhere
+    pub fn depends_on_unknown(&self, id: ExprId) -> Result<bool, ExprError> {
+        let mut visits = vec![Visit::NotVisited; self.exprs.len()];
+        Ok(self.evaluate_all(id, None, &mut visits)?.is_none())
+    }
+
+    /// Replaces every expression that doesn't depend on the unknown with its value
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::expr::{Expr, ExprArena, Op};
+    ///
+    /// let mut arena = ExprArena::<i128>::new();
+    /// let two = arena.constant(2);
+    /// let three = arena.constant(3);
+    /// let six = arena.op(Op::Mul, two, three);
+    /// let x = arena.unknown();
+    /// let sum = arena.op(Op::Add, six, x);
+    /// arena.fold().unwrap();
+    /// assert_eq!(arena.get(six), &Expr::Constant(6));
+    /// assert_eq!(arena.get(sum), &Expr::Op(Op::Add, six, x));
+    /// assert_eq!(arena.display(sum).to_string(), "(6 + x)");
+    /// ```
+    pub fn fold(&mut self) -> Result<(), ExprError> {
+        let mut visits = vec![Visit::NotVisited; self.exprs.len()];
+        for index in 0..self.exprs.len() {
+            let id = ExprId(index);
+            if let Expr::Op(..) = self.exprs[index] {
+                if let Some(value) = self.evaluate_all(id, None, &mut visits)? {
+                    self.exprs[index] = Expr::Constant(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the value of the unknown that makes two expressions equal. The unknown must appear
+    /// on only one side, and every operation on the way to it must have the unknown on only one
+    /// side too, so that each one can be undone in turn
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::expr::{ExprArena, ExprError, Op};
+    ///
+    /// let mut arena = ExprArena::<i128>::new();
+    /// let x = arena.unknown();
+    /// let ten = arena.constant(10);
+    /// let zero = arena.constant(0);
+    /// let ten_minus_x = arena.op(Op::Sub, ten, x);
+    /// let four = arena.constant(4);
+    /// assert_eq!(arena.solve(ten_minus_x, four), Ok(6));
+    ///
+    /// let x_squared = arena.op(Op::Mul, x, x);
+    /// assert_eq!(arena.solve(x_squared, four), Err(ExprError::NonLinear(x_squared)));
+    ///
+    /// let zero_x = arena.op(Op::Mul, zero, x);
+    /// assert_eq!(arena.solve(zero_x, zero), Err(ExprError::Ambiguous));
+    /// assert_eq!(arena.solve(zero_x, four), Err(ExprError::NoSolution));
+    /// assert_eq!(arena.solve(ten, four), Err(ExprError::NoUnknown));
+    ///
+    /// // x / 0 has no value, whatever x is
+    /// let x_over_zero = arena.op(Op::Div, x, zero);
+    /// assert_eq!(arena.solve(x_over_zero, zero), Err(ExprError::DivisionByZero));
+    ///
+    /// // 0 / x is 0 for any x but 0 itself, so it can never be 4, and can't pin x down to be 0
+    /// let zero_over_x = arena.op(Op::Div, zero, x);
+    /// assert_eq!(arena.solve(zero_over_x, four), Err(ExprError::NoSolution));
+    /// assert_eq!(arena.solve(zero_over_x, zero), Err(ExprError::Ambiguous));
+    ///
+    /// let ten_over_x = arena.op(Op::Div, ten, x);
+    /// let five = arena.constant(5);
+    /// assert_eq!(arena.solve(ten_over_x, five), Ok(2));
+    /// assert_eq!(arena.solve(ten_over_x, zero), Err(ExprError::NoSolution));
+    /// ```
+    pub fn solve(&self, lhs: ExprId, rhs: ExprId) -> Result<N, ExprError> {
+        let mut visits = vec![Visit::NotVisited; self.exprs.len()];
+        let left = self.evaluate_all(lhs, None, &mut visits)?;
+        let right = self.evaluate_all(rhs, None, &mut visits)?;
+        let (mut current, mut target) = match (left, right) {
+            (None, Some(right)) => (lhs, right),
+            (Some(left), None) => (rhs, left),
+            (Some(_), Some(_)) => return Err(ExprError::NoUnknown),
+            (None, None) => return Err(ExprError::NonLinear(lhs)),
+        };
+        let known = |id: ExprId| match visits[id.0] {
+            Visit::Done(value) => value,
+            _ => unreachable!("Expression was not evaluated"),
+        };
+
+        // Undo each operation on the way down to the unknown
+        loop {
+            let (op, a, b) = match self.exprs[current.0] {
+                Expr::Unknown => return Ok(target),
+                Expr::Op(op, a, b) => (op, a, b),
+                _ => unreachable!("Only the unknown and operations can depend on the unknown"),
+            };
+            let (next, next_target) = match (known(a), known(b)) {
+                (None, None) => return Err(ExprError::NonLinear(current)),
+                (Some(_), Some(_)) => unreachable!("Operation with known sides was unknown"),
+                // x + b = t, x - b = t, x * b = t, x / b = t
+                (None, Some(b_value)) => (
+                    a,
+                    match op {
+                        Op::Add => target.sub(b_value)?,
+                        Op::Sub => target.add(b_value)?,
+                        Op::Mul => divide_to_solve(target, b_value)?,
+                        Op::Div if b_value == N::zero() => return Err(ExprError::DivisionByZero),
+                        Op::Div => target.mul(b_value)?,
+                    },
+                ),
+                // a + x = t, a - x = t, a * x = t, a / x = t
+                (Some(a_value), None) => (
+                    b,
+                    match op {
+                        Op::Add => target.sub(a_value)?,
+                        Op::Sub => a_value.sub(target)?,
+                        Op::Mul => divide_to_solve(target, a_value)?,
+                        // a / x = t means x * t = a, except x can never be 0. With a = 0,
+                        // every other x gives 0
+                        Op::Div if a_value == N::zero() => {
+                            return Err(if target == N::zero() {
+                                ExprError::Ambiguous
+                            } else {
+                                ExprError::NoSolution
+                            });
+                        }
+                        Op::Div => divide_to_solve(a_value, target)?,
+                    },
+                ),
+            };
+            current = next;
+            target = next_target;
+        }
+    }
+
+    /// Shows an expression with brackets around every operation and `x` for the unknown
+    pub fn display(&self, id: ExprId) -> ExprDisplay<'_, N> {
+        ExprDisplay { arena: self, id }
+    }
+}
+
+/// Solves `x * by = target`
+fn divide_to_solve<N: Number>(target: N, by: N) -> Result<N, ExprError> {
+    match (by == N::zero(), target == N::zero()) {
+        (true, true) => Err(ExprError::Ambiguous),
+        (true, false) => Err(ExprError::NoSolution),
+        _ => target.div(by),
+    }
+}
+
+/// Shows an expression from an [`ExprArena`]
+pub struct ExprDisplay<'a, N> {
+    arena: &'a ExprArena<N>,
+    id: ExprId,
+}
+
+impl<'a, N: Number> Display for ExprDisplay<'a, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.arena.get(self.id) {
+            Expr::Undefined => write!(f, "?"),
+            Expr::Constant(n) => write!(f, "{}", n),
+            Expr::Unknown => write!(f, "x"),
+            Expr::Op(op, a, b) => write!(
+                f,
+                "({} {} {})",
+                self.arena.display(*a),
+                op.symbol(),
+                self.arena.display(*b)
+            ),
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//! Points, directions, rotations and extents. `y` increases downwards, so "up" is towards smaller `y`, the
+//! same as rows in a [`Grid`](super::Grid).
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// A point or vector in two dimensions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// The distance between two points moving only horizontally and vertically
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    ///
+    /// assert_eq!(Point2::new(2, 18).manhattan(Point2::new(-2, 15)), 7);
+    /// assert_eq!(Point2::new(1u8, 1).manhattan(Point2::new(3, 0)), 3);
+    /// ```
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance between two points when diagonal moves are allowed
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    ///
+    /// // The tail of a rope needs to move if it's more than 1 away from the head
+    /// assert_eq!(Point2::new(0, 0).chebyshev(Point2::new(1, 1)), 1);
+    /// assert_eq!(Point2::new(0, 0).chebyshev(Point2::new(2, 1)), 2);
+    /// ```
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point2<T> {
+    /// Moves one step in a direction
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::{Direction, Point2};
+    ///
+    /// let point = Point2::new(0i64, 0);
+    /// assert_eq!(point.step(Direction::Up), Point2::new(0, -1));
+    /// assert_eq!(point.step(Direction::DownRight), Point2::new(1, 1));
+    /// ```
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.unit()
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Point2<T> {
+    /// Rotates a vector around the origin
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::{Point2, Rotation};
+    ///
+    /// let vector = Point2::new(2, -1);
+    /// assert_eq!(vector.rotate(Rotation::Clockwise), Point2::new(1, 2));
+    /// assert_eq!(vector.rotate(Rotation::Half), Point2::new(-2, 1));
+    /// ```
+    pub fn rotate(self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::None => self,
+            Rotation::Clockwise => Point2::new(-self.y, self.x),
+            Rotation::Half => Point2::new(-self.x, -self.y),
+            Rotation::Anticlockwise => Point2::new(self.y, -self.x),
+        }
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Scales a vector
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A point or vector in three dimensions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// The distance between two points moving along one axis at a time
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point3;
+    ///
+    /// assert_eq!(Point3::new(1, 1, 1).manhattan(Point3::new(2, 1, 1)), 1);
+    /// assert_eq!(Point3::new(1, 1, 1).manhattan(Point3::new(3, 0, 4)), 6);
+    /// ```
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// The distance between two points when diagonal moves are allowed
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point3;
+    ///
+    /// assert_eq!(Point3::new(1, 1, 1).chebyshev(Point3::new(3, 0, 4)), 3);
+    /// ```
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+/// Scales a vector
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// A direction to move in. The first four are the orthogonal directions, the rest are diagonal.
+/// The coordinates in use along one axis. Counts how many things sit at each coordinate, so the
+/// smallest and largest stay up to date in O(log n) as things are added and removed.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Extent;
+///
+/// let mut extent = Extent::new();
+/// extent.add(3);
+/// extent.add(-1);
+/// extent.add(3);
+/// assert_eq!((extent.min(), extent.max()), (Some(-1), Some(3)));
+/// extent.remove(3);
+/// assert_eq!(extent.max(), Some(3)); // Something else is still at 3
+/// extent.remove(3);
+/// assert_eq!(extent.max(), Some(-1));
+/// extent.remove(-1);
+/// assert_eq!(extent.min(), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Extent(BTreeMap<i64, usize>);
+
+impl Extent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one more thing at a coordinate
+    pub fn add(&mut self, coordinate: i64) {
+        *self.0.entry(coordinate).or_insert(0) += 1;
+    }
+
+    /// Counts one less thing at a coordinate. Does nothing if nothing was there
+    pub fn remove(&mut self, coordinate: i64) {
+        if let Some(count) = self.0.get_mut(&coordinate) {
+            *count -= 1;
+            if *count == 0 {
+                self.0.remove(&coordinate);
+            }
+        }
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.0.keys().next().copied()
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.0.keys().next_back().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The four directions that don't move diagonally, clockwise from up
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, clockwise from up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// How far a single step in this direction moves in `x` and `y`
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    /// A vector of a single step in this direction
+    pub fn unit<T: From<i8>>(&self) -> Point2<T> {
+        let (x, y) = self.offset();
+        Point2::new(T::from(x as i8), T::from(y as i8))
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        !Direction::ORTHOGONAL.contains(self)
+    }
+
+    /// Rotates the direction by some number of quarter turns
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::{Direction, Rotation};
+    ///
+    /// assert_eq!(Direction::Up.rotate(Rotation::Clockwise), Direction::Right);
+    /// assert_eq!(Direction::UpRight.rotate(Rotation::Half), Direction::DownLeft);
+    /// ```
+    pub fn rotate(self, rotation: Rotation) -> Self {
+        let index = Direction::ALL.iter().position(|d| *d == self).unwrap();
+        Direction::ALL[(index + 2 * rotation.quarter_turns()) % 8]
+    }
+
+    /// Turns a quarter turn anticlockwise
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Direction;
+    ///
+    /// assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    /// assert_eq!(Direction::DownLeft.turn_left(), Direction::DownRight);
+    /// ```
+    pub fn turn_left(self) -> Self {
+        self.rotate(Rotation::Anticlockwise)
+    }
+
+    /// Turns a quarter turn clockwise
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Direction;
+    ///
+    /// assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    /// assert_eq!(Direction::UpLeft.turn_right(), Direction::UpRight);
+    /// ```
+    pub fn turn_right(self) -> Self {
+        self.rotate(Rotation::Clockwise)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(Rotation::Half)
+    }
+}
+
+/// Reads `U`, `D`, `L` or `R`, an arrow (`^`, `v`, `<` or `>`) or a compass point (`N`, `S`, `E`
+/// or `W`)
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Direction;
+///
+/// assert_eq!(Direction::try_from('U'), Ok(Direction::Up));
+/// assert_eq!(Direction::try_from('<'), Ok(Direction::Left));
+/// assert_eq!(Direction::try_from('S'), Ok(Direction::Down));
+/// assert!(Direction::try_from('x').is_err());
+/// ```
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | '^' | 'N' => Ok(Direction::Up),
+            'D' | 'v' | 'S' => Ok(Direction::Down),
+            'L' | '<' | 'W' => Ok(Direction::Left),
+            'R' | '>' | 'E' => Ok(Direction::Right),
+            _ => Err(format!("Invalid direction: {}", c)),
+        }
+    }
+}
+
+/// Reads any single character direction, or a diagonal compass point such as `NE`
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Direction;
+///
+/// assert_eq!("R".parse(), Ok(Direction::Right));
+/// assert_eq!("NW".parse(), Ok(Direction::UpLeft));
+/// assert!("up".parse::<Direction>().is_err());
+/// ```
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, None) => Direction::try_from(c),
+            (Some('N'), Some('E'), None) => Ok(Direction::UpRight),
+            (Some('N'), Some('W'), None) => Ok(Direction::UpLeft),
+            (Some('S'), Some('E'), None) => Ok(Direction::DownRight),
+            (Some('S'), Some('W'), None) => Ok(Direction::DownLeft),
+            _ => Err(format!("Invalid direction: {}", s)),
+        }
+    }
+}
+
+/// A turn by a whole number of quarter turns
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    Clockwise,
+    Half,
+    Anticlockwise,
+}
+
+impl Rotation {
+    /// Creates a rotation from a number of clockwise quarter turns, which may be negative
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Rotation;
+    ///
+    /// assert_eq!(Rotation::from_quarter_turns(5), Rotation::Clockwise);
+    /// assert_eq!(Rotation::from_quarter_turns(-1), Rotation::Anticlockwise);
+    /// ```
+    pub fn from_quarter_turns(turns: i64) -> Self {
+        match turns.rem_euclid(4) {
+            0 => Rotation::None,
+            1 => Rotation::Clockwise,
+            2 => Rotation::Half,
+            _ => Rotation::Anticlockwise,
+        }
+    }
+
+    /// The number of clockwise quarter turns, from 0 to 3
+    pub fn quarter_turns(&self) -> usize {
+        match self {
+            Rotation::None => 0,
+            Rotation::Clockwise => 1,
+            Rotation::Half => 2,
+            Rotation::Anticlockwise => 3,
+        }
+    }
+
+    /// Turns by this rotation and then by another
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Rotation;
+    ///
+    /// assert_eq!(Rotation::Clockwise.then(Rotation::Half), Rotation::Anticlockwise);
+    /// ```
+    pub fn then(self, other: Rotation) -> Self {
+        Rotation::from_quarter_turns((self.quarter_turns() + other.quarter_turns()) as i64)
+    }
+
+    pub fn inverse(self) -> Self {
+        Rotation::from_quarter_turns(-(self.quarter_turns() as i64))
+    }
+}
+
+/// Reads `L` (anticlockwise) or `R` (clockwise)
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::{Direction, Rotation};
+///
+/// let facing = Direction::Right.rotate(Rotation::try_from('L').unwrap());
+/// assert_eq!(facing, Direction::Up);
+/// ```
+impl TryFrom<char> for Rotation {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'L' => Ok(Rotation::Anticlockwise),
+            'R' => Ok(Rotation::Clockwise),
+            _ => Err(format!("Invalid rotation: {}", c)),
+        }
+    }
+}
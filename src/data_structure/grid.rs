@@ -0,0 +1,412 @@
+pub use crate::data_structure::geometry::Direction;
+use crate::data_structure::geometry::Point2;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid, where `x` is the column and `y` is the row. `(0, 0)` is the top left.
+pub type Point = Point2<usize>;
+
+/// A dense, rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to the same value
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::new(3, 2, '.');
+    /// assert_eq!(grid.width(), 3);
+    /// assert_eq!(grid.height(), 2);
+    /// assert_eq!(grid.get(Point::new(2, 1)), Some(&'.'));
+    /// ```
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Swaps rows and columns
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_lines(["abc", "def"], |c| c);
+    /// assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    /// ```
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned().collect::<Vec<_>>())
+            .collect();
+        Grid {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Rotates the grid a quarter turn clockwise
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_lines(["abc", "def"], |c| c);
+    /// assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+    /// ```
+    pub fn rotate_clockwise(&self) -> Self {
+        let mut rotated = self.transpose();
+        rotated.flip_horizontal();
+        rotated
+    }
+
+    /// Rotates the grid a quarter turn anticlockwise
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_lines(["abc", "def"], |c| c);
+    /// assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad");
+    /// ```
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let mut rotated = self.transpose();
+        rotated.flip_vertical();
+        rotated
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from a list of rows. Every row must be the same length
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(grid.width(), 2);
+    ///
+    /// assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "Row {} has length {}, expected {}",
+                    y,
+                    row.len(),
+                    width
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Creates a grid from lines of text, converting each character into a cell.
+    ///
+    /// Panics if the lines are not all the same length
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::from_lines(["303", "255"], |c| c.to_digit(10).unwrap());
+    /// assert_eq!(grid[Point::new(1, 1)], 5);
+    /// ```
+    pub fn from_lines<I, S, F>(lines: I, f: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: Fn(char) -> T,
+    {
+        let rows = lines
+            .into_iter()
+            .map(|line| line.as_ref().chars().map(&f).collect())
+            .collect();
+        Self::from_rows(rows).expect("Lines in grid were not all the same length")
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks if a point is inside the grid
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point).then(|| point.y * self.width + point.x)
+    }
+
+    /// Gets a cell, or `None` if the point is outside the grid
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::from_lines(["ab", "cd"], |c| c);
+    /// assert_eq!(grid.get(Point::new(1, 0)), Some(&'b'));
+    /// assert_eq!(grid.get(Point::new(2, 0)), None);
+    /// ```
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Sets a cell, returning the old value. If the point is outside the grid nothing changes and
+    /// `None` is returned
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let mut grid = Grid::new(2, 2, '.');
+    /// assert_eq!(grid.set(Point::new(1, 1), '#'), Some('.'));
+    /// assert_eq!(grid[Point::new(1, 1)], '#');
+    /// assert_eq!(grid.set(Point::new(2, 2), '#'), None);
+    /// ```
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.get_mut(point)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Moves one step from a point, if that stays inside the grid
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Direction, Grid, Point};
+    ///
+    /// let grid = Grid::new(2, 2, '.');
+    /// assert_eq!(grid.step(Point::new(0, 0), Direction::Right), Some(Point::new(1, 0)));
+    /// assert_eq!(grid.step(Point::new(0, 0), Direction::Up), None);
+    /// ```
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.offset();
+        let next = Point::new(
+            point.x.checked_add_signed(dx)?,
+            point.y.checked_add_signed(dy)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    /// The points directly above, below, left and right of a point that are inside the grid
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::new(3, 3, '.');
+    /// assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+    /// assert_eq!(
+    ///     grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+    ///     vec![Point::new(1, 0), Point::new(0, 1)]
+    /// );
+    /// ```
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// The points around a point, including diagonals, that are inside the grid
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::new(3, 3, '.');
+    /// assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    /// assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+    /// ```
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// Gets the cells seen when looking from a point in a direction, nearest first, until the edge
+    /// of the grid. The starting point is not included
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Direction, Grid, Point};
+    ///
+    /// let grid = Grid::from_lines(["30373", "25512", "65332"], |c| c.to_digit(10).unwrap());
+    /// let left: Vec<_> = grid.ray(Point::new(3, 1), Direction::Left).map(|(_, h)| *h).collect();
+    /// assert_eq!(left, vec![5, 5, 2]);
+    /// assert_eq!(grid.ray(Point::new(3, 0), Direction::Up).count(), 0);
+    ///
+    /// // A tree is visible if every tree between it and an edge is shorter
+    /// let tree = Point::new(1, 1);
+    /// let visible = [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+    ///     .into_iter()
+    ///     .any(|d| grid.ray(tree, d).all(|(_, h)| *h < grid[tree]));
+    /// assert_eq!(visible, true);
+    /// ```
+    pub fn ray(&self, from: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> + '_ {
+        std::iter::successors(self.step(from, direction), move |point| {
+            self.step(*point, direction)
+        })
+        .map(move |point| (point, &self[point]))
+    }
+
+    /// Gets a row of the grid
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Iterates over every row, from the top
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 2], &[3, 4]]);
+    /// ```
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y).unwrap())
+    }
+
+    /// Iterates down a column of the grid. Columns outside the grid are empty
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &4]);
+    /// assert_eq!(grid.column(2).count(), 0);
+    /// ```
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Iterates over every column, from the left
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Iterates over every point in the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Iterates over every cell along with its point, row by row
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::{Grid, Point};
+    ///
+    /// let grid = Grid::from_lines([".#", "#."], |c| c == '#');
+    /// let walls: Vec<_> = grid.iter().filter(|(_, wall)| **wall).map(|(p, _)| p).collect();
+    /// assert_eq!(walls, vec![Point::new(1, 0), Point::new(0, 1)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Creates a new grid of the same size by converting every cell
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&mut self) {
+        let width = self.width;
+        if width > 0 {
+            self.cells.chunks_mut(width).for_each(|row| row.reverse());
+        }
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            for x in 0..self.width {
+                self.cells
+                    .swap(y * self.width + x, (self.height - 1 - y) * self.width + x);
+            }
+        }
+    }
+
+    /// Displays the grid by converting each cell to a character
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::grid::Grid;
+    ///
+    /// let grid = Grid::from_lines([".#", "#."], |c| c == '#');
+    /// let display = grid.display(|wall| if *wall { '█' } else { ' ' });
+    /// assert_eq!(display.to_string(), " █\n█ ");
+    /// ```
+    pub fn display<F: Fn(&T) -> char>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f }
+    }
+}
+
+/// Gets a cell, panicking if the point is outside the grid
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "Point ({}, {}) is outside the {}x{} grid",
+                point.x, point.y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!(
+                "Point ({}, {}) is outside the {}x{} grid",
+                point.x, point.y, width, height
+            )
+        })
+    }
+}
+
+/// Displays a grid, converting each cell to a character. Rows are separated by new lines
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<'a, T, F: Fn(&T) -> char> Display for GridDisplay<'a, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", (self.f)(cell))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(|c| *c))
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+/// A set of values stored as the ranges they cover rather than one by one.
+///
+/// Ranges are half open, the same as [`Range`]. They are kept sorted, with any ranges that overlap
+/// or touch merged together, so inserting, removing and looking up a single range are O(log n) in
+/// the number of ranges stored (plus the number of ranges merged or removed).
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::IntervalSet;
+///
+/// let mut set = IntervalSet::new();
+/// set.insert(0..5);
+/// set.insert(10..15);
+/// set.insert(5..8); // Touches 0..5, so they merge
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..8, 10..15]);
+/// assert_eq!(set.covered_length(), 13);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Start to end of each range
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of separate ranges in the set
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The range starting closest before or at a value
+    fn range_at(&self, value: T) -> Option<Range<T>> {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .map(|(&start, &end)| start..end)
+    }
+
+    /// Adds every value in a range to the set. Empty ranges are ignored
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// let mut set: IntervalSet<i32> = [0..2, 4..6, 8..10].into_iter().collect();
+    /// set.insert(1..9);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..10]);
+    ///
+    /// set.insert(20..20);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some(before) = self.range_at(start) {
+            if before.end >= start {
+                start = before.start;
+                end = end.max(before.end);
+            }
+        }
+        let merged: Vec<T> = self.ranges.range(start..=end).map(|(s, _)| *s).collect();
+        for merged_start in merged {
+            let merged_end = self.ranges.remove(&merged_start).unwrap();
+            end = end.max(merged_end);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes every value in a range out of the set
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// let mut set: IntervalSet<i32> = [0..10, 12..20].into_iter().collect();
+    /// set.remove(3..5);
+    /// set.remove(8..15);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..3, 5..8, 15..20]);
+    /// ```
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        if let Some((&start, &end)) = self.ranges.range(..range.start).next_back() {
+            if end > range.start {
+                self.ranges.insert(start, range.start);
+                if end > range.end {
+                    self.ranges.insert(range.end, end);
+                }
+            }
+        }
+        let inside: Vec<(T, T)> = self
+            .ranges
+            .range(range.start..range.end)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+        for (start, end) in inside {
+            self.ranges.remove(&start);
+            if end > range.end {
+                self.ranges.insert(range.end, end);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear()
+    }
+
+    /// Checks if a single value is in the set
+    pub fn contains(&self, value: T) -> bool {
+        self.range_at(value).is_some_and(|r| r.end > value)
+    }
+
+    /// Checks if every value in a range is in the set. This is always true for an empty range
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// let set: IntervalSet<i32> = [0..5, 6..9].into_iter().collect();
+    /// assert_eq!(set.contains_range(&(1..5)), true);
+    /// assert_eq!(set.contains_range(&(4..7)), false);
+    /// assert_eq!(set.contains_range(&(5..5)), true);
+    /// ```
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        range.is_empty()
+            || self
+                .range_at(range.start)
+                .is_some_and(|r| r.end >= range.end)
+    }
+
+    /// Checks if any value in a range is in the set. This is always false for an empty range
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// let set: IntervalSet<i32> = [0..5, 10..15].into_iter().collect();
+    /// assert_eq!(set.overlaps(&(4..6)), true);
+    /// assert_eq!(set.overlaps(&(5..10)), false);
+    /// assert_eq!(set.overlaps(&(12..12)), false);
+    /// ```
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        !range.is_empty()
+            && self
+                .ranges
+                .range(..range.end)
+                .next_back()
+                .is_some_and(|(_, end)| *end > range.start)
+    }
+
+    /// Iterates over the ranges in the set, in order
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.ranges.iter())
+    }
+
+    /// The smallest range containing the whole set
+    pub fn span(&self) -> Option<Range<T>> {
+        let start = *self.ranges.keys().next()?;
+        let end = *self.ranges.values().next_back()?;
+        Some(start..end)
+    }
+
+    /// Every value in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    /// Every value in this set that isn't in the other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Every value in both sets
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// let a: IntervalSet<i32> = [0..5, 8..12].into_iter().collect();
+    /// let b: IntervalSet<i32> = [3..10].into_iter().collect();
+    /// assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![3..5, 8..10]);
+    /// assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![0..12]);
+    /// assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![0..3, 10..12]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            intersection.insert(x.start.max(y.start)..x.end.min(y.end));
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        intersection
+    }
+
+    /// The ranges inside `within` that are not in the set, in order
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::IntervalSet;
+    ///
+    /// // Sensor coverage of a row, with one cell left uncovered
+    /// let covered: IntervalSet<i64> = [-2..11, 12..25].into_iter().collect();
+    /// assert_eq!(covered.gaps(0..21).collect::<Vec<_>>(), vec![11..12]);
+    /// assert_eq!(covered.gaps(-5..0).collect::<Vec<_>>(), vec![-5..-2]);
+    /// ```
+    pub fn gaps(&self, within: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let mut position = within.start;
+        let from = self
+            .range_at(within.start)
+            .map_or(within.start, |r| r.start)
+            .min(within.end);
+        self.ranges
+            .range(from..within.end)
+            .map(|(start, end)| (*start, *end))
+            .chain(std::iter::once((within.end, within.end)))
+            .filter_map(move |(start, end)| {
+                let gap = position..start.min(within.end);
+                position = position.max(end);
+                (!gap.is_empty()).then_some(gap)
+            })
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// The number of values in the set
+    pub fn covered_length(&self) -> T {
+        self.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+/// Iterates over the ranges in an [`IntervalSet`]
+pub struct Iter<'a, T>(btree_map::Iter<'a, T, T>);
+
+impl<'a, T: Copy> Iterator for Iter<'a, T> {
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(start, end)| *start..*end)
+    }
+}
+
+impl<'a, T: Copy + Ord> IntoIterator for &'a IntervalSet<T> {
+    type Item = Range<T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A cache of results for a recursive function, so each state is only worked out once.
+///
+/// The function being cached takes the `Memo` as an argument and calls
+/// [`get_or_compute`](Memo::get_or_compute), passing a closure that works out the result for a
+/// state the cache hasn't seen. The closure is given the `Memo` back so it can recurse.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::Memo;
+///
+/// fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_compute(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+/// assert_eq!(memo.misses(), 89);
+/// assert_eq!(memo.hits(), 87);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    /// Keys in the order they were added, only kept when there's a bound
+    order: VecDeque<K>,
+    bound: Option<usize>,
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            bound: None,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    /// Creates a cache that keeps every result
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cache that holds at most `bound` results. Once full, the oldest result is thrown
+    /// away to make room for each new one
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Memo;
+    ///
+    /// let mut memo = Memo::with_bound(2);
+    /// for n in [1, 2, 3, 1] {
+    ///     memo.get_or_compute(n, |_| n * 10);
+    /// }
+    /// assert_eq!(memo.len(), 2);
+    /// assert_eq!(memo.evictions(), 2); // 1 made way for 3, then 2 made way for 1 again
+    /// assert_eq!(memo.hits(), 0);
+    /// ```
+    pub fn with_bound(bound: usize) -> Self {
+        Memo {
+            bound: Some(bound),
+            ..Self::default()
+        }
+    }
+
+    /// Gets the cached result for a key, or works it out with `compute` and caches it. `compute`
+    /// is given the cache so that it can look up other keys
+    pub fn get_or_compute<F: FnOnce(&mut Self) -> V>(&mut self, key: K, compute: F) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Caches a result directly
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.bound == Some(0) {
+            return;
+        }
+        if let Some(bound) = self.bound {
+            if !self.cache.contains_key(&key) {
+                while self.cache.len() >= bound {
+                    let oldest = self
+                        .order
+                        .pop_front()
+                        .expect("Bounded cache lost its order");
+                    self.cache.remove(&oldest);
+                    self.evictions += 1;
+                }
+                self.order.push_back(key.clone());
+            }
+        }
+        self.cache.insert(key, value);
+    }
+
+    /// Looks up a key without working anything out. This doesn't count as a hit or a miss
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The number of cached results
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Throws away every cached result, but keeps the statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+    }
+
+    /// How many times a result was found in the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// How many times a result had to be worked out
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// How many results have been thrown away to stay within the bound
+    pub fn evictions(&self) -> usize {
+        self.evictions
+    }
+}
@@ -0,0 +1,282 @@
+//! Shortest path searches over graphs that are described by closures rather than stored up front.
+//!
+//! Each search takes:
+//! - one or more start nodes, all of which begin at no cost
+//! - a key function that says when two nodes are the same state. Nodes with the same key are only
+//!   expanded once, so the key can leave out anything that doesn't affect the rest of the search
+//! - a neighbour function giving the nodes reachable from a node (and for weighted searches, the
+//!   cost of getting there)
+//! - a goal check
+//!
+//! ```rust
+//! use advent_of_code_2022::data_structure::grid::Point;
+//! use advent_of_code_2022::data_structure::search::bfs;
+//! use advent_of_code_2022::data_structure::Grid;
+//!
+//! let heights = Grid::from_lines(
+//!     ["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"],
+//!     |c| match c {
+//!         'S' => b'a',
+//!         'E' => b'z',
+//!         c => c as u8,
+//!     },
+//! );
+//! let heights = &heights;
+//! let climbable = |from: &Point| {
+//!     let from = *from;
+//!     heights
+//!         .neighbours4(from)
+//!         .filter(move |to| heights[*to] <= heights[from] + 1)
+//!         .collect::<Vec<_>>()
+//! };
+//! let end = Point::new(5, 2);
+//!
+//! let from_start = bfs([Point::new(0, 0)], |p| *p, climbable, |p| *p == end);
+//! assert_eq!(from_start.cost(), Some(31));
+//!
+//! let starts = heights.iter().filter(|(_, h)| **h == b'a').map(|(p, _)| p);
+//! let from_any_a = bfs(starts, |p| *p, climbable, |p| *p == end);
+//! assert_eq!(from_any_a.cost(), Some(29));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path to a goal along with what it cost to get there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<N, C> {
+    pub cost: C,
+    /// Every node from the start to the goal, including both
+    pub path: Vec<N>,
+}
+
+/// What a search found, if anything, and how much work it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    pub found: Option<Found<N, C>>,
+    /// How many nodes were taken off the frontier and had their neighbours looked at
+    pub expanded: usize,
+}
+
+impl<N, C: Copy> SearchResult<N, C> {
+    pub fn cost(&self) -> Option<C> {
+        self.found.as_ref().map(|found| found.cost)
+    }
+
+    pub fn path(&self) -> Option<&[N]> {
+        self.found.as_ref().map(|found| found.path.as_slice())
+    }
+
+    /// The node at the end of the path
+    pub fn goal(&self) -> Option<&N> {
+        self.path().and_then(|path| path.last())
+    }
+}
+
+/// Every node the search has reached, along with the index of the node it was reached from
+struct Arena<N> {
+    nodes: Vec<(N, Option<usize>)>,
+}
+
+impl<N: Clone> Arena<N> {
+    fn new() -> Self {
+        Arena { nodes: Vec::new() }
+    }
+
+    fn add(&mut self, node: N, parent: Option<usize>) -> usize {
+        self.nodes.push((node, parent));
+        self.nodes.len() - 1
+    }
+
+    fn get(&self, index: usize) -> &N {
+        &self.nodes[index].0
+    }
+
+    fn path_to(&self, index: usize) -> Vec<N> {
+        let mut path: Vec<N> = std::iter::successors(Some(index), |index| self.nodes[*index].1)
+            .map(|index| self.nodes[index].0.clone())
+            .collect();
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search, where every step costs 1. The cost is the number of steps taken.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::search::bfs;
+///
+/// // Reach 10 from 1 by doubling or adding one
+/// let result = bfs([1], |n| *n, |n| [n * 2, n + 1], |n| *n == 10);
+/// assert_eq!(result.path(), Some(&[1, 2, 4, 5, 10][..]));
+/// assert_eq!(result.cost(), Some(4));
+///
+/// let result = bfs([1], |n| *n, |n| if *n < 5 { vec![n + 1] } else { vec![] }, |n| *n == 10);
+/// assert_eq!(result.found, None);
+/// assert_eq!(result.expanded, 5);
+/// ```
+pub fn bfs<N, K, S, KF, NF, I, G>(
+    starts: S,
+    key: KF,
+    mut neighbours: NF,
+    mut is_goal: G,
+) -> SearchResult<N, usize>
+where
+    N: Clone,
+    K: Hash + Eq,
+    S: IntoIterator<Item = N>,
+    KF: Fn(&N) -> K,
+    NF: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut seen = HashSet::new();
+    let mut frontier = VecDeque::new();
+    for start in starts {
+        if seen.insert(key(&start)) {
+            frontier.push_back((arena.add(start, None), 0));
+        }
+    }
+
+    let mut expanded = 0;
+    while let Some((index, cost)) = frontier.pop_front() {
+        if is_goal(arena.get(index)) {
+            let path = arena.path_to(index);
+            return SearchResult {
+                found: Some(Found { cost, path }),
+                expanded,
+            };
+        }
+        expanded += 1;
+        for next in neighbours(arena.get(index)) {
+            if seen.insert(key(&next)) {
+                frontier.push_back((arena.add(next, Some(index)), cost + 1));
+            }
+        }
+    }
+    SearchResult {
+        found: None,
+        expanded,
+    }
+}
+
+/// Dijkstra's algorithm, for when steps can cost different amounts. Costs must not be negative and
+/// `C::default()` must be zero.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::search::dijkstra;
+///
+/// // Going straight to 'c' costs more than going via 'b'
+/// let edges = |n: &char| match n {
+///     'a' => vec![('b', 1), ('c', 10)],
+///     'b' => vec![('c', 2)],
+///     _ => vec![],
+/// };
+/// let result = dijkstra(['a'], |n| *n, edges, |n| *n == 'c');
+/// assert_eq!(result.cost(), Some(3));
+/// assert_eq!(result.path(), Some(&['a', 'b', 'c'][..]));
+/// ```
+pub fn dijkstra<N, C, K, S, KF, NF, I, G>(
+    starts: S,
+    key: KF,
+    neighbours: NF,
+    is_goal: G,
+) -> SearchResult<N, C>
+where
+    N: Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    K: Hash + Eq,
+    S: IntoIterator<Item = N>,
+    KF: Fn(&N) -> K,
+    NF: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(starts, key, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search, which is Dijkstra's algorithm guided towards the goal by an estimate of the
+/// remaining cost. The estimate must never be more than the real remaining cost, or the path found
+/// might not be the shortest.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Point2;
+/// use advent_of_code_2022::data_structure::search::{astar, dijkstra};
+///
+/// // An open plane, so every step costs 1
+/// let goal = Point2::new(20i64, 20);
+/// let steps = |p: &Point2<i64>| {
+///     let p = *p;
+///     [Point2::new(1, 0), Point2::new(-1, 0), Point2::new(0, 1), Point2::new(0, -1)]
+///         .map(|step| (p + step, 1))
+/// };
+/// let start = Point2::new(0, 0);
+///
+/// let guided = astar([start], |p| *p, steps, |p| p.manhattan(goal), |p| *p == goal);
+/// let unguided = dijkstra([start], |p| *p, steps, |p| *p == goal);
+/// assert_eq!(guided.cost(), Some(40));
+/// assert_eq!(unguided.cost(), Some(40));
+/// assert!(guided.expanded < unguided.expanded);
+/// ```
+pub fn astar<N, C, K, S, KF, NF, I, H, G>(
+    starts: S,
+    key: KF,
+    mut neighbours: NF,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> SearchResult<N, C>
+where
+    N: Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    K: Hash + Eq,
+    S: IntoIterator<Item = N>,
+    KF: Fn(&N) -> K,
+    NF: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut best = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+    for start in starts {
+        if best.insert(key(&start), C::default()).is_none() {
+            let estimate = heuristic(&start);
+            frontier.push(Reverse((estimate, C::default(), arena.add(start, None))));
+        }
+    }
+
+    let mut expanded = 0;
+    while let Some(Reverse((_, cost, index))) = frontier.pop() {
+        let node = arena.get(index);
+        // A cheaper way to this node was found after this entry was queued
+        if best.get(&key(node)).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        if is_goal(node) {
+            let path = arena.path_to(index);
+            return SearchResult {
+                found: Some(Found { cost, path }),
+                expanded,
+            };
+        }
+        expanded += 1;
+        for (next, step) in neighbours(node) {
+            let next_cost = cost + step;
+            let next_key = key(&next);
+            if best.get(&next_key).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+            best.insert(next_key, next_cost);
+            let estimate = next_cost + heuristic(&next);
+            frontier.push(Reverse((estimate, next_cost, arena.add(next, Some(index)))));
+        }
+    }
+    SearchResult {
+        found: None,
+        expanded,
+    }
+}
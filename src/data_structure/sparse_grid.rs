@@ -0,0 +1,303 @@
+use crate::data_structure::geometry::{Extent, Point2};
+use crate::data_structure::grid::{Grid, Point};
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The smallest rectangle containing every occupied cell of a [`SparseGrid`]. Both corners are
+/// inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point2<i64>,
+    pub max: Point2<i64>,
+}
+
+impl Bounds {
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn contains(&self, point: Point2<i64>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Converts a point to its position in a dense grid whose top left is the minimum corner
+    fn to_dense(self, point: Point2<i64>) -> Point {
+        Point::new(
+            (point.x - self.min.x) as usize,
+            (point.y - self.min.y) as usize,
+        )
+    }
+}
+
+/// A grid with no fixed size where only occupied cells take up memory. Coordinates can be negative
+/// and `y` increases downwards, the same as [`Grid`].
+///
+/// The bounding box of the occupied cells is kept up to date as cells are inserted and removed,
+/// by counting the occupied cells in each row and column.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Point2;
+/// use advent_of_code_2022::data_structure::SparseGrid;
+///
+/// let mut grid = SparseGrid::new();
+/// grid.insert(Point2::new(-1, 0), '#');
+/// grid.insert(Point2::new(1, 2), 'o');
+/// assert_eq!(grid.len(), 2);
+/// assert_eq!(grid.to_string(), "#..\n...\n..o");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2<i64>, T>,
+    xs: Extent,
+    ys: Extent,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            xs: Extent::new(),
+            ys: Extent::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of occupied cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing every occupied cell, or `None` if the grid is empty
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    /// use advent_of_code_2022::data_structure::SparseGrid;
+    ///
+    /// let mut grid = SparseGrid::new();
+    /// assert_eq!(grid.bounds(), None);
+    /// grid.insert(Point2::new(3, -2), ());
+    /// grid.insert(Point2::new(-1, 4), ());
+    /// let bounds = grid.bounds().unwrap();
+    /// assert_eq!((bounds.min, bounds.max), (Point2::new(-1, -2), Point2::new(3, 4)));
+    /// assert_eq!((bounds.width(), bounds.height()), (5, 7));
+    /// ```
+    pub fn bounds(&self) -> Option<Bounds> {
+        Some(Bounds {
+            min: Point2::new(self.xs.min()?, self.ys.min()?),
+            max: Point2::new(self.xs.max()?, self.ys.max()?),
+        })
+    }
+
+    pub fn contains(&self, point: Point2<i64>) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point2<i64>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point2<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Occupies a cell, returning the value that was there before
+    pub fn insert(&mut self, point: Point2<i64>, value: T) -> Option<T> {
+        let previous = self.cells.insert(point, value);
+        if previous.is_none() {
+            self.xs.add(point.x);
+            self.ys.add(point.y);
+        }
+        previous
+    }
+
+    /// Empties a cell, returning the value that was there
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    /// use advent_of_code_2022::data_structure::SparseGrid;
+    ///
+    /// let mut grid = SparseGrid::new();
+    /// grid.insert(Point2::new(0, 0), 'a');
+    /// grid.insert(Point2::new(5, 5), 'b');
+    /// assert_eq!(grid.remove(Point2::new(5, 5)), Some('b'));
+    /// assert_eq!(grid.bounds().unwrap().max, Point2::new(0, 0));
+    ///
+    /// // Another cell in the same column keeps the bounds as wide
+    /// grid.insert(Point2::new(3, 0), 'c');
+    /// grid.insert(Point2::new(3, 2), 'd');
+    /// grid.remove(Point2::new(3, 2));
+    /// assert_eq!(grid.bounds().unwrap().max, Point2::new(3, 0));
+    /// ```
+    pub fn remove(&mut self, point: Point2<i64>) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+        self.xs.remove(point.x);
+        self.ys.remove(point.y);
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.xs.clear();
+        self.ys.clear();
+    }
+
+    /// Iterates over the occupied cells in no particular order
+    pub fn iter(&self) -> hash_map::Iter<'_, Point2<i64>, T> {
+        self.cells.iter()
+    }
+
+    /// Displays the occupied extent of the grid by converting each cell to a character. Empty
+    /// cells are given to the mapping as `None`
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    /// use advent_of_code_2022::data_structure::SparseGrid;
+    ///
+    /// let mut grid = SparseGrid::new();
+    /// grid.insert(Point2::new(0, 0), 1);
+    /// grid.insert(Point2::new(2, 1), 2);
+    /// let display = grid.display(|cell| cell.map(|n| if *n > 1 { '@' } else { '#' }).unwrap_or(' '));
+    /// assert_eq!(display.to_string(), "#  \n  @");
+    /// ```
+    pub fn display<F: Fn(Option<&T>) -> char>(&self, f: F) -> SparseGridDisplay<'_, T, F> {
+        SparseGridDisplay { grid: self, f }
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Creates a sparse grid from the cells of a dense grid that pass a check. Dense grid points
+    /// keep the same coordinates
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    /// use advent_of_code_2022::data_structure::{Grid, SparseGrid};
+    ///
+    /// let dense = Grid::from_lines(["..#", "#.."], |c| c);
+    /// let sparse = SparseGrid::from_grid(&dense, |c| *c == '#');
+    /// assert_eq!(sparse.len(), 2);
+    /// assert!(sparse.contains(Point2::new(2, 0)));
+    /// ```
+    pub fn from_grid<F: Fn(&T) -> bool>(grid: &Grid<T>, is_occupied: F) -> Self {
+        grid.iter()
+            .filter(|(_, cell)| is_occupied(cell))
+            .map(|(point, cell)| (Point2::new(point.x as i64, point.y as i64), cell.clone()))
+            .collect()
+    }
+
+    /// Creates a dense grid covering the occupied extent, filling empty cells with a value. The
+    /// top left of the dense grid is the minimum corner of [`bounds`](Self::bounds)
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point2;
+    /// use advent_of_code_2022::data_structure::SparseGrid;
+    /// use advent_of_code_2022::data_structure::grid::Point;
+    ///
+    /// let mut sparse = SparseGrid::new();
+    /// sparse.insert(Point2::new(-2, -2), 'a');
+    /// sparse.insert(Point2::new(-1, -1), 'b');
+    /// let dense = sparse.to_grid('.');
+    /// assert_eq!(dense[Point::new(1, 1)], 'b');
+    /// assert_eq!(dense.to_string(), "a.\n.b");
+    /// ```
+    pub fn to_grid(&self, empty: T) -> Grid<T> {
+        Grid::from(self).map(|cell| cell.clone().unwrap_or_else(|| empty.clone()))
+    }
+}
+
+/// Occupies every cell of a dense grid that isn't `None`
+impl<T: Clone> From<&Grid<Option<T>>> for SparseGrid<T> {
+    fn from(grid: &Grid<Option<T>>) -> Self {
+        grid.iter()
+            .filter_map(|(point, cell)| {
+                cell.as_ref()
+                    .map(|cell| (Point2::new(point.x as i64, point.y as i64), cell.clone()))
+            })
+            .collect()
+    }
+}
+
+/// A dense copy of the occupied extent, with `None` for empty cells
+impl<T: Clone> From<&SparseGrid<T>> for Grid<Option<T>> {
+    fn from(sparse: &SparseGrid<T>) -> Self {
+        let bounds = match sparse.bounds() {
+            Some(bounds) => bounds,
+            None => return Grid::new(0, 0, None),
+        };
+        let mut grid = Grid::new(bounds.width(), bounds.height(), None);
+        for (&point, cell) in &sparse.cells {
+            grid[bounds.to_dense(point)] = Some(cell.clone());
+        }
+        grid
+    }
+}
+
+impl<T> FromIterator<(Point2<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point2<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point2<i64>, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point2<i64>, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point2<i64>, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Displays the occupied extent of a sparse grid, converting each cell to a character. Rows are
+/// separated by new lines
+pub struct SparseGridDisplay<'a, T, F> {
+    grid: &'a SparseGrid<T>,
+    f: F,
+}
+
+impl<'a, T, F: Fn(Option<&T>) -> char> Display for SparseGridDisplay<'a, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bounds = match self.grid.bounds() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                writeln!(f)?;
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                write!(f, "{}", (self.f)(self.grid.get(Point2::new(x, y))))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Empty cells are shown as `.`
+impl Display for SparseGrid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(|c| c.copied().unwrap_or('.')))
+    }
+}
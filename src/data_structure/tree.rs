@@ -0,0 +1,271 @@
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut};
+
+/// Refers to a node in a [`Tree`]. Ids are only meaningful for the tree that created them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The position of the node in the tree. Nodes are numbered in the order they were added,
+    /// starting with the root at 0
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node<K, T> {
+    key: Option<K>,
+    value: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+/// A tree where every node holds a value, and every node other than the root is known to its
+/// parent by a key (such as a file name).
+///
+/// Nodes are stored in a single Vec and refer to each other by [`NodeId`], so there's no need for
+/// `Rc<RefCell>` and the whole tree can be borrowed or changed as normal. Nodes can't be removed.
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::Tree;
+///
+/// let mut tree = Tree::new(0);
+/// let a = tree.add_child(tree.root(), "a", 0).unwrap();
+/// tree.add_child(a, "e", 584).unwrap();
+/// tree.add_child(tree.root(), "b.txt", 14848514).unwrap();
+///
+/// assert_eq!(tree.child(tree.root(), "a"), Some(a));
+/// assert_eq!(tree.parent(a), Some(tree.root()));
+/// assert_eq!(tree.node_count(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree<K, T> {
+    nodes: Vec<Node<K, T>>,
+}
+
+impl<K, T> Tree<K, T> {
+    /// Creates a tree containing only a root node
+    pub fn new(root: T) -> Self {
+        Tree {
+            nodes: vec![Node {
+                key: None,
+                value: root,
+                parent: None,
+                children: Vec::new(),
+            }],
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// The number of nodes in the tree, including the root
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn get(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].value
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id.0].value
+    }
+
+    /// The key the node's parent knows it by. The root has no key
+    pub fn key(&self, id: NodeId) -> Option<&K> {
+        self.nodes[id.0].key.as_ref()
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    /// The children of a node in the order they were added
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].children
+    }
+
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.nodes[id.0].children.is_empty()
+    }
+
+    /// The parent of a node, its parent, and so on up to the root
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Tree;
+    ///
+    /// let mut tree = Tree::new("/");
+    /// let a = tree.add_child(tree.root(), "a", "dir").unwrap();
+    /// let e = tree.add_child(a, "e", "dir").unwrap();
+    /// let path: Vec<_> = tree.ancestors(e).filter_map(|id| tree.key(id)).collect();
+    /// assert_eq!(path, vec![&"a"]);
+    /// assert_eq!(tree.depth(e), 2);
+    /// ```
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |id| self.parent(*id))
+    }
+
+    /// How many steps a node is from the root
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.ancestors(id).count()
+    }
+
+    /// Visits a node and everything below it, each node before its children
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Tree;
+    ///
+    /// let mut tree = Tree::new('/');
+    /// let a = tree.add_child(tree.root(), 'a', 'a').unwrap();
+    /// tree.add_child(a, 'e', 'e').unwrap();
+    /// tree.add_child(tree.root(), 'd', 'd').unwrap();
+    ///
+    /// let order: String = tree.pre_order(tree.root()).map(|id| tree[id]).collect();
+    /// assert_eq!(order, "/aed");
+    /// ```
+    pub fn pre_order(&self, from: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![from];
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            stack.extend(self.children(id).iter().rev());
+            Some(id)
+        })
+    }
+
+    /// Visits a node and everything below it, each node after its children
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Tree;
+    ///
+    /// let mut tree = Tree::new('/');
+    /// let a = tree.add_child(tree.root(), 'a', 'a').unwrap();
+    /// tree.add_child(a, 'e', 'e').unwrap();
+    /// tree.add_child(tree.root(), 'd', 'd').unwrap();
+    ///
+    /// let order: String = tree.post_order(tree.root()).map(|id| tree[id]).collect();
+    /// assert_eq!(order, "ead/");
+    /// ```
+    pub fn post_order(&self, from: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        // Each node is pushed twice, the second time once its children have been visited
+        let mut stack = vec![(from, false)];
+        std::iter::from_fn(move || loop {
+            let (id, children_visited) = stack.pop()?;
+            if children_visited {
+                return Some(id);
+            }
+            stack.push((id, true));
+            stack.extend(self.children(id).iter().rev().map(|child| (*child, false)));
+        })
+    }
+
+    /// Works out a value for every node from its own value and the values already worked out for
+    /// its children, starting at the leaves
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Tree;
+    ///
+    /// // Directories have no size of their own
+    /// let mut tree = Tree::new(0);
+    /// let a = tree.add_child(tree.root(), "a", 0).unwrap();
+    /// tree.add_child(a, "f", 29116).unwrap();
+    /// tree.add_child(a, "g", 2557).unwrap();
+    /// tree.add_child(tree.root(), "b.txt", 14848514).unwrap();
+    ///
+    /// let sizes = tree.fold_up(|size, children| size + children.iter().copied().sum::<u64>());
+    /// assert_eq!(sizes[a], 31673);
+    /// assert_eq!(sizes[tree.root()], 14880187);
+    /// ```
+    pub fn fold_up<A, F>(&self, mut f: F) -> NodeValues<A>
+    where
+        F: FnMut(&T, &[&A]) -> A,
+    {
+        // Children are always added after their parents, so going backwards through the nodes
+        // reaches every child before its parent
+        let mut values: Vec<Option<A>> = (0..self.nodes.len()).map(|_| None).collect();
+        for index in (0..self.nodes.len()).rev() {
+            let (before, after) = values.split_at_mut(index + 1);
+            let children: Vec<&A> = self.nodes[index]
+                .children
+                .iter()
+                .map(|child| after[child.0 - index - 1].as_ref().unwrap())
+                .collect();
+            before[index] = Some(f(&self.nodes[index].value, &children));
+        }
+        NodeValues(values.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+impl<K: PartialEq, T> Tree<K, T> {
+    /// Finds the child of a node with a key
+    pub fn child<Q>(&self, parent: NodeId, key: &Q) -> Option<NodeId>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.children(parent)
+            .iter()
+            .copied()
+            .find(|child| self.key(*child).is_some_and(|k| k.borrow() == key))
+    }
+
+    /// Adds a child to a node. If the node already has a child with the same key nothing is added
+    /// and the id of the existing child is returned as an error
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::Tree;
+    ///
+    /// let mut tree = Tree::new(());
+    /// let a = tree.add_child(tree.root(), "a", ()).unwrap();
+    /// assert_eq!(tree.add_child(tree.root(), "a", ()), Err(a));
+    /// ```
+    pub fn add_child(&mut self, parent: NodeId, key: K, value: T) -> Result<NodeId, NodeId> {
+        if let Some(existing) = self.child(parent, &key) {
+            return Err(existing);
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            key: Some(key),
+            value,
+            parent: Some(parent),
+            children: Vec::new(),
+        });
+        self.nodes[parent.0].children.push(id);
+        Ok(id)
+    }
+}
+
+impl<K, T> Index<NodeId> for Tree<K, T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &Self::Output {
+        self.get(id)
+    }
+}
+
+impl<K, T> IndexMut<NodeId> for Tree<K, T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        self.get_mut(id)
+    }
+}
+
+/// A value for every node in a [`Tree`], as made by [`Tree::fold_up`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeValues<A>(Vec<A>);
+
+impl<A> NodeValues<A> {
+    /// Iterates over every node's value, in the order the nodes were added
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &A)> + '_ {
+        self.0.iter().enumerate().map(|(i, a)| (NodeId(i), a))
+    }
+}
+
+impl<A> Index<NodeId> for NodeValues<A> {
+    type Output = A;
+
+    fn index(&self, id: NodeId) -> &Self::Output {
+        &self.0[id.0]
+    }
+}
@@ -0,0 +1,232 @@
+use crate::data_structure::geometry::{Extent, Point3};
+use std::collections::{HashSet, VecDeque};
+
+/// The six steps to the cubes sharing a face with a cube
+const FACE_STEPS: [Point3<i64>; 6] = [
+    Point3 { x: 1, y: 0, z: 0 },
+    Point3 { x: -1, y: 0, z: 0 },
+    Point3 { x: 0, y: 1, z: 0 },
+    Point3 { x: 0, y: -1, z: 0 },
+    Point3 { x: 0, y: 0, z: 1 },
+    Point3 { x: 0, y: 0, z: -1 },
+];
+
+/// The surface area of the cubes in a [`VoxelGrid`], counted in faces
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SurfaceArea {
+    /// Every face that isn't touching another cube, including faces of air pockets
+    pub total: usize,
+    /// Only the faces that can be reached from outside
+    pub external: usize,
+}
+
+/// A set of unit cubes in three dimensions, along with the smallest box containing them all
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::geometry::Point3;
+/// use advent_of_code_2022::data_structure::voxel_grid::{SurfaceArea, VoxelGrid};
+/// use advent_of_code_2022::input::numbers::extract;
+///
+/// let droplet = "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n3,2,5\n\
+///     2,1,5\n2,3,5";
+/// let grid: VoxelGrid = droplet
+///     .lines()
+///     .map(|line| Point3::from(extract::<(i64, i64, i64)>(line).unwrap()))
+///     .collect();
+/// assert_eq!(grid.surface_area(), SurfaceArea { total: 64, external: 58 });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VoxelGrid {
+    cubes: HashSet<Point3<i64>>,
+    xs: Extent,
+    ys: Extent,
+    zs: Extent,
+}
+
+impl VoxelGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of cubes
+    pub fn len(&self) -> usize {
+        self.cubes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cubes.is_empty()
+    }
+
+    pub fn contains(&self, cube: Point3<i64>) -> bool {
+        self.cubes.contains(&cube)
+    }
+
+    /// Adds a cube, returning false if it was already there
+    pub fn insert(&mut self, cube: Point3<i64>) -> bool {
+        if !self.cubes.insert(cube) {
+            return false;
+        }
+        self.xs.add(cube.x);
+        self.ys.add(cube.y);
+        self.zs.add(cube.z);
+        true
+    }
+
+    /// Takes a cube out, returning false if it wasn't there
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point3;
+    /// use advent_of_code_2022::data_structure::voxel_grid::VoxelGrid;
+    ///
+    /// let mut grid: VoxelGrid = (0..3).map(|x| Point3::new(x, 0, 0)).collect();
+    /// grid.remove(Point3::new(1, 0, 0));
+    /// assert_eq!(grid.bounds(), Some((Point3::new(0, 0, 0), Point3::new(2, 0, 0))));
+    /// grid.remove(Point3::new(2, 0, 0));
+    /// assert_eq!(grid.bounds(), Some((Point3::new(0, 0, 0), Point3::new(0, 0, 0))));
+    /// grid.remove(Point3::new(0, 0, 0));
+    /// assert_eq!(grid.bounds(), None);
+    /// ```
+    pub fn remove(&mut self, cube: Point3<i64>) -> bool {
+        if !self.cubes.remove(&cube) {
+            return false;
+        }
+        self.xs.remove(cube.x);
+        self.ys.remove(cube.y);
+        self.zs.remove(cube.z);
+        true
+    }
+
+    /// The smallest and largest corners of the box containing every cube, both inclusive
+    pub fn bounds(&self) -> Option<(Point3<i64>, Point3<i64>)> {
+        Some((
+            Point3::new(self.xs.min()?, self.ys.min()?, self.zs.min()?),
+            Point3::new(self.xs.max()?, self.ys.max()?, self.zs.max()?),
+        ))
+    }
+
+    /// Checks if a point is inside the bounding box
+    pub fn in_bounds(&self, point: Point3<i64>) -> bool {
+        self.bounds().is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&point.x)
+                && (min.y..=max.y).contains(&point.y)
+                && (min.z..=max.z).contains(&point.z)
+        })
+    }
+
+    /// Iterates over the cubes in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = Point3<i64>> + '_ {
+        self.cubes.iter().copied()
+    }
+
+    /// The six positions that share a face with a position
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point3;
+    /// use advent_of_code_2022::data_structure::voxel_grid::VoxelGrid;
+    ///
+    /// let origin = Point3::new(0, 0, 0);
+    /// assert_eq!(VoxelGrid::neighbours6(origin).count(), 6);
+    /// assert!(VoxelGrid::neighbours6(origin).all(|p| p.manhattan(origin) == 1));
+    /// ```
+    pub fn neighbours6(point: Point3<i64>) -> impl Iterator<Item = Point3<i64>> {
+        FACE_STEPS.into_iter().map(move |step| point + step)
+    }
+
+    /// Finds every empty position that can be reached from outside the cubes without passing
+    /// through one, within a box one larger than the bounding box on every side. Empty positions
+    /// inside the bounding box that aren't in this set are enclosed air pockets
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::data_structure::geometry::Point3;
+    /// use advent_of_code_2022::data_structure::voxel_grid::VoxelGrid;
+    ///
+    /// // A hollow 3x3x3 cube
+    /// let grid: VoxelGrid = (0..27)
+    ///     .map(|i| Point3::new(i % 3, i / 3 % 3, i / 9))
+    ///     .filter(|p| *p != Point3::new(1, 1, 1))
+    ///     .collect();
+    /// let exterior = grid.exterior();
+    /// assert!(!exterior.contains(&Point3::new(1, 1, 1)));
+    /// assert!(exterior.contains(&Point3::new(-1, 1, 1)));
+    /// assert_eq!(grid.interior().collect::<Vec<_>>(), vec![Point3::new(1, 1, 1)]);
+    /// ```
+    pub fn exterior(&self) -> HashSet<Point3<i64>> {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return HashSet::new(),
+        };
+        let one = Point3::new(1, 1, 1);
+        let (min, max) = (min - one, max + one);
+        let in_box = |p: &Point3<i64>| {
+            (min.x..=max.x).contains(&p.x)
+                && (min.y..=max.y).contains(&p.y)
+                && (min.z..=max.z).contains(&p.z)
+        };
+
+        let mut exterior = HashSet::from([min]);
+        let mut queue = VecDeque::from([min]);
+        while let Some(point) = queue.pop_front() {
+            for next in Self::neighbours6(point) {
+                if in_box(&next) && !self.contains(next) && exterior.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        exterior
+    }
+
+    /// Every empty position that is completely enclosed by cubes
+    pub fn interior(&self) -> impl Iterator<Item = Point3<i64>> + '_ {
+        let exterior = self.exterior();
+        let (min, max) = self.bounds().unwrap_or_default();
+        let (width, height) = (max.x - min.x + 1, max.y - min.y + 1);
+        let count = if self.is_empty() {
+            0
+        } else {
+            width * height * (max.z - min.z + 1)
+        };
+        (0..count)
+            .map(move |i| {
+                Point3::new(
+                    min.x + i % width,
+                    min.y + i / width % height,
+                    min.z + i / (width * height),
+                )
+            })
+            .filter(move |p| !self.contains(*p) && !exterior.contains(p))
+    }
+
+    /// Counts the faces of cubes that don't touch another cube
+    pub fn surface_area(&self) -> SurfaceArea {
+        let exterior = self.exterior();
+        let mut area = SurfaceArea {
+            total: 0,
+            external: 0,
+        };
+        for neighbour in self.iter().flat_map(Self::neighbours6) {
+            if !self.contains(neighbour) {
+                area.total += 1;
+                if exterior.contains(&neighbour) {
+                    area.external += 1;
+                }
+            }
+        }
+        area
+    }
+}
+
+impl FromIterator<Point3<i64>> for VoxelGrid {
+    fn from_iter<I: IntoIterator<Item = Point3<i64>>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl Extend<Point3<i64>> for VoxelGrid {
+    fn extend<I: IntoIterator<Item = Point3<i64>>>(&mut self, iter: I) {
+        for cube in iter {
+            self.insert(cube);
+        }
+    }
+}
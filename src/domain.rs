@@ -1,5 +1,6 @@
 pub mod crane9000;
 pub mod crane9001;
+pub mod filesystem;
 pub mod rock_paper_scissors;
 pub mod rucksack;
 pub mod search_party;
@@ -1,49 +1,42 @@
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::input::diagnostic::Diagnostic;
+use crate::input::fixed_width::FixedWidthTable;
+use crate::input::line_format::FromLine;
 use std::cmp::max;
 use std::ops::Deref;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// A move instruction
+/// ```rust
+/// use std::str::FromStr;
+/// use advent_of_code_2022::domain::crane9001::Move;
+///
+/// # fn main() -> Result<(), String> {
+/// let m = Move::from_str("move 1 from 2 to 3")?;
+/// assert_eq!(m.amount, 1);
+/// assert_eq!(m.from, 2);
+/// assert_eq!(m.to, 3);
+///
+/// assert!(Move::from_str("move 1 from 2").is_err());
+/// let error = Move::from_str("move 1 frm 2 to 3").unwrap_err();
+/// assert_eq!(error.message(), "Expected ' from '");
+/// let error = Move::from_str("lift 1 from 2 to 3").unwrap_err();
+/// assert_eq!(error.span(), 0..5);
+///
+/// // Surrounding whitespace is ignored
+/// assert_eq!(Move::from_str("  move 1 from 2 to 3 ")?, m);
+/// let error = Move::from_str("  lift 1 from 2 to 3").unwrap_err();
+/// assert_eq!(error.span(), 2..7);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromLine)]
+#[line("move {amount} from {from} to {to}")]
 pub struct Move {
     pub amount: usize,
     pub from: usize,
     pub to: usize,
 }
 
-impl FromStr for Move {
-    type Err = String;
-
-    /// Parses a string instruction
-    /// ```rust
-    /// use std::str::FromStr;
-    /// use advent_of_code_2022::domain::crane9001::Move;
-    ///
-    /// # fn main() -> Result<(), String> {
-    /// let m = Move::from_str("move 1 from 2 to 3")?;
-    /// assert_eq!(m.amount, 1);
-    /// assert_eq!(m.from, 2);
-    /// assert_eq!(m.to, 3);
-    /// # Ok(())
-    /// # }
-    /// ```
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        lazy_static! {
-            static ref MOVE_RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
-        }
-        let caps = MOVE_RE
-            .captures(s)
-            .ok_or_else(|| format!("String not a move instruction: {}", s))?;
-        Ok(Self {
-            amount: caps.get(1).unwrap().as_str().parse().unwrap(),
-            from: caps.get(2).unwrap().as_str().parse().unwrap(),
-            to: caps.get(3).unwrap().as_str().parse().unwrap(),
-        })
-    }
-}
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Instruction {
     Move(Move),
@@ -56,7 +49,7 @@ impl From<Move> for Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = String;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Move::from_str(s)?.into())
@@ -235,26 +228,26 @@ impl From<Vec<String>> for Stacks {
     /// ];
     /// let stacks = Stacks::from(input);
     /// assert_eq!(stacks.get_message(), "WOO".to_string());
+    ///
+    /// // More than 9 stacks
+    /// let input = vec![
+    ///     "                                        [L]".to_string(),
+    ///     "[H] [E] [L] [L] [O] [W] [O] [R] [L] [D] [S]".to_string(),
+    ///     " 1   2   3   4   5   6   7   8   9   10  11 ".to_string(),
+    /// ];
+    /// let stacks = Stacks::from(input);
+    /// assert_eq!(stacks.get_message(), "HELLOWORLDL".to_string());
     /// ```
     fn from(mut strings: Vec<String>) -> Self {
-        strings.reverse();
-        let mut iter = strings.into_iter();
-        let first = iter.next().unwrap();
-        let len = (first.len() + 1) / 4;
-        let mut stacks = vec![Stack::default(); len];
+        let footer = strings.pop().expect("No stack numbers found");
+        let table = FixedWidthTable::from(footer.as_str());
+        let mut stacks = vec![Stack::default(); table.len()];
 
-        for string in iter {
-            let crates: Vec<String> = string
-                .chars()
-                .chunks(4)
-                .into_iter()
-                .map(|chunk| chunk.collect())
-                .collect();
-            for (i, string) in crates.iter().enumerate() {
-                let c = string.chars().nth(1).unwrap();
-                match c {
-                    'A'..='Z' | 'a'..='z' => stacks[i].0.push(c),
-                    _ => {}
+        for string in strings.iter().rev() {
+            for (stack, cell) in stacks.iter_mut().zip(table.cells(string)) {
+                if let Some(c) = cell.and_then(|cell| cell.chars().find(char::is_ascii_alphabetic))
+                {
+                    stack.0.push(c);
                 }
             }
         }
@@ -0,0 +1,642 @@
+use crate::data_structure::tree::{NodeId, NodeValues};
+use crate::data_structure::Tree;
+use crate::input::diagnostic::Diagnostic;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Where `cd` goes to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CdTarget {
+    Root,
+    Parent,
+    Child(String),
+}
+
+/// A line from a terminal session, either a command or part of the output of `ls`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalLine {
+    Cd(CdTarget),
+    Ls,
+    Dir(String),
+    File { size: u64, name: String },
+}
+
+impl FromStr for TerminalLine {
+    type Err = Diagnostic;
+
+    /// Parses a command (`$ cd name`, `$ cd ..`, `$ cd /` or `$ ls`) or a line of `ls` output
+    /// (`dir name` or `size name`)
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use advent_of_code_2022::domain::filesystem::{CdTarget, TerminalLine};
+    ///
+    /// # fn main() -> Result<(), String> {
+    /// assert_eq!(TerminalLine::from_str("$ cd ..")?, TerminalLine::Cd(CdTarget::Parent));
+    /// assert_eq!(TerminalLine::from_str("$ ls")?, TerminalLine::Ls);
+    /// assert_eq!(TerminalLine::from_str("dir a")?, TerminalLine::Dir("a".to_string()));
+    /// assert_eq!(
+    ///     TerminalLine::from_str("14848514 b.txt")?,
+    ///     TerminalLine::File { size: 14848514, name: "b.txt".to_string() }
+    /// );
+    ///
+    /// let error = TerminalLine::from_str("$ rm -rf /").unwrap_err();
+    /// assert_eq!(error.span(), 2..4);
+    /// let error = TerminalLine::from_str("12x b.txt").unwrap_err();
+    /// assert_eq!(error.span(), 0..3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(command) = s.strip_prefix("$ ") {
+            let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+            return match (name, argument) {
+                ("cd", "") => Err(Diagnostic::whole_line("cd needs a directory", s)),
+                ("cd", "/") => Ok(TerminalLine::Cd(CdTarget::Root)),
+                ("cd", "..") => Ok(TerminalLine::Cd(CdTarget::Parent)),
+                ("cd", child) => Ok(TerminalLine::Cd(CdTarget::Child(child.to_string()))),
+                ("ls", "") => Ok(TerminalLine::Ls),
+                ("ls", _) => Err(Diagnostic::new(
+                    "ls doesn't take any arguments",
+                    s,
+                    5..s.len(),
+                )),
+                _ => Err(Diagnostic::new("Unknown command", s, 2..2 + name.len())),
+            };
+        }
+        let (first, name) = s
+            .split_once(' ')
+            .ok_or_else(|| Diagnostic::whole_line("Expected 'dir name' or 'size name'", s))?;
+        if first == "dir" {
+            return Ok(TerminalLine::Dir(name.to_string()));
+        }
+        let size = first
+            .parse()
+            .map_err(|_| Diagnostic::new("File size is not a number", s, 0..first.len()))?;
+        Ok(TerminalLine::File {
+            size,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Something in a filesystem
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Directory,
+    File(u64),
+}
+
+impl Entry {
+    pub fn is_directory(&self) -> bool {
+        matches!(self, Entry::Directory)
+    }
+}
+
+/// A directory tree, where each node's key is its name
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    tree: Tree<String, Entry>,
+}
+
+impl FileSystem {
+    pub fn tree(&self) -> &Tree<String, Entry> {
+        &self.tree
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.tree.root()
+    }
+
+    pub fn entry(&self, id: NodeId) -> Entry {
+        self.tree[id]
+    }
+
+    /// The name of an entry. The root is called `/`
+    pub fn name(&self, id: NodeId) -> &str {
+        self.tree.key(id).map(String::as_str).unwrap_or("/")
+    }
+
+    /// The full path of an entry
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::filesystem::Transcript;
+    ///
+    /// let mut transcript = Transcript::new();
+    /// for line in ["$ cd /", "$ ls", "dir a", "$ cd a", "$ ls", "584 i"] {
+    ///     transcript.replay(line).unwrap();
+    /// }
+    /// let fs = transcript.finish().unwrap();
+    /// let i = fs.find("/a/i").unwrap();
+    /// assert_eq!(fs.path(i), "/a/i");
+    /// assert_eq!(fs.path(fs.root()), "/");
+    /// assert_eq!(fs.find("/b"), None);
+    /// ```
+    pub fn path(&self, id: NodeId) -> String {
+        path(&self.tree, id)
+    }
+
+    /// Finds an entry by its full path
+    pub fn find(&self, path: &str) -> Option<NodeId> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self.root(), |id, name| self.tree.child(id, name))
+    }
+
+    /// The size of every entry, where a directory's size is the total size of all the files
+    /// anywhere inside it
+    pub fn sizes(&self) -> NodeValues<u64> {
+        self.tree.fold_up(|entry, children| match entry {
+            Entry::File(size) => *size,
+            Entry::Directory => children.iter().copied().sum(),
+        })
+    }
+
+    /// Every directory, including the root
+    pub fn directories(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.tree
+            .pre_order(self.root())
+            .filter(|id| self.tree[*id].is_directory())
+    }
+
+    /// Every file
+    pub fn files(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.tree
+            .pre_order(self.root())
+            .filter(|id| !self.tree[*id].is_directory())
+    }
+
+    /// Finds every entry matching a glob. `*` matches any part of a name, `?` matches one
+    /// character and `**` matches any number of directories. A pattern without a `/` is matched
+    /// against names, otherwise it's matched against full paths
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::filesystem::Transcript;
+    ///
+    /// let fs = Transcript::replay_all([
+    ///     "$ ls", "dir a", "10 b.txt", "$ cd a", "$ ls", "20 c.txt", "30 d.dat",
+    /// ])
+    /// .unwrap();
+    /// let paths = |pattern| fs.glob(pattern).map(|id| fs.path(id)).collect::<Vec<_>>();
+    /// assert_eq!(paths("*.txt"), vec!["/a/c.txt", "/b.txt"]);
+    /// assert_eq!(paths("/*.txt"), vec!["/b.txt"]);
+    /// assert_eq!(paths("/**.dat"), vec!["/a/d.dat"]);
+    /// assert_eq!(paths("/a/?.*"), vec!["/a/c.txt", "/a/d.dat"]);
+    ///
+    /// // Lots of stars don't make matching slow
+    /// let many_stars = format!("{}b", "**a".repeat(20));
+    /// assert_eq!(paths(&many_stars), Vec::<String>::new());
+    /// ```
+    pub fn glob<'a>(&'a self, pattern: &str) -> impl Iterator<Item = NodeId> + 'a {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let whole_path = pattern.contains(&'/');
+        self.tree.pre_order(self.root()).filter(move |id| {
+            let text: Vec<char> = if whole_path {
+                self.path(*id).chars().collect()
+            } else {
+                self.name(*id).chars().collect()
+            };
+            glob_matches(&pattern, &text)
+        })
+    }
+
+    /// The `n` largest files, largest first
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::filesystem::Transcript;
+    ///
+    /// let fs = Transcript::replay_all([
+    ///     "$ ls", "dir a", "10 b", "$ cd a", "$ ls", "20 c", "5 d",
+    /// ])
+    /// .unwrap();
+    /// let paths = |ids: Vec<_>| ids.into_iter().map(|id| fs.path(id)).collect::<Vec<_>>();
+    /// assert_eq!(paths(fs.largest_files(2)), vec!["/a/c", "/b"]);
+    /// assert_eq!(paths(fs.directories_over(25)), vec!["/"]);
+    /// ```
+    pub fn largest_files(&self, n: usize) -> Vec<NodeId> {
+        let mut files: Vec<NodeId> = self.files().collect();
+        files.sort_by_key(|id| match self.tree[*id] {
+            Entry::File(size) => Reverse(size),
+            Entry::Directory => unreachable!("Only files were collected"),
+        });
+        files.truncate(n);
+        files
+    }
+
+    /// Every directory whose size is more than `threshold`, in the order they were found
+    pub fn directories_over(&self, threshold: u64) -> Vec<NodeId> {
+        let sizes = self.sizes();
+        self.directories()
+            .filter(|id| sizes[*id] > threshold)
+            .collect()
+    }
+
+    /// Every directory with its size, largest first, like `du | sort -rn`
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::filesystem::Transcript;
+    ///
+    /// let fs = Transcript::replay_all([
+    ///     "$ ls", "dir a", "dir b", "10 c", "$ cd a", "$ ls", "20 d", "$ cd ..", "$ cd b", "$ ls",
+    ///     "5 e",
+    /// ])
+    /// .unwrap();
+    /// let usage: Vec<_> = fs
+    ///     .disk_usage()
+    ///     .into_iter()
+    ///     .map(|(id, size)| (fs.path(id), size))
+    ///     .collect();
+    /// assert_eq!(
+    ///     usage,
+    ///     vec![("/".to_string(), 35), ("/a".to_string(), 20), ("/b".to_string(), 5)]
+    /// );
+    /// ```
+    pub fn disk_usage(&self) -> Vec<(NodeId, u64)> {
+        let sizes = self.sizes();
+        let mut usage: Vec<(NodeId, u64)> = self.directories().map(|id| (id, sizes[id])).collect();
+        usage.sort_by_key(|(_, size)| Reverse(*size));
+        usage
+    }
+
+    /// Shows the filesystem as an indented listing like the `tree` command, with the size of every
+    /// entry
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::filesystem::Transcript;
+    ///
+    /// let fs = Transcript::replay_all([
+    ///     "$ ls", "dir a", "14848514 b.txt", "$ cd a", "$ ls", "dir e", "29116 f", "$ cd e",
+    ///     "$ ls", "584 i",
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(
+    ///     fs.tree_display().to_string(),
+    ///     "/ (14.2M)\n\
+    ///      ├── a/ (29.0K)\n\
+    ///      │   ├── e/ (584)\n\
+    ///      │   │   └── i (584)\n\
+    ///      │   └── f (28.4K)\n\
+    ///      └── b.txt (14.2M)\n"
+    /// );
+    /// ```
+    pub fn tree_display(&self) -> TreeDisplay<'_> {
+        TreeDisplay {
+            filesystem: self,
+            sizes: self.sizes(),
+        }
+    }
+}
+
+/// Formats a number of bytes like `du -h`, using powers of 1024
+///
+/// ```rust
+/// use advent_of_code_2022::domain::filesystem::human_size;
+///
+/// assert_eq!(human_size(584), "584");
+/// assert_eq!(human_size(94853), "92.6K");
+/// assert_eq!(human_size(48381165), "46.1M");
+/// assert_eq!(human_size(1048575), "1.0M");
+/// ```
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    // Stops 1023.99 from being shown as 1024.0
+    while size >= 1023.95 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", size, UNITS[unit])
+}
+
+/// Matches a glob against some text, see [`FileSystem::glob`].
+///
+/// Rather than backtracking, this tracks every position in the text the pattern so far could have
+/// matched up to, which keeps it to O(pattern × text) however many stars there are
+fn glob_matches(pattern: &[char], text: &[char]) -> bool {
+    let mut reachable = vec![false; text.len() + 1];
+    reachable[0] = true;
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        let mut next = vec![false; text.len() + 1];
+        match rest {
+            ['*', '*', ..] => {
+                // Anything at all, so every position from the first reachable one onwards
+                let first = reachable.iter().position(|r| *r).unwrap_or(next.len());
+                next[first..].fill(true);
+                rest = &rest[2..];
+            }
+            ['*', ..] => {
+                // Anything within a name, so a position stays reachable until a '/'
+                for i in 0..next.len() {
+                    next[i] = reachable[i] || (i > 0 && next[i - 1] && text[i - 1] != '/');
+                }
+                rest = &rest[1..];
+            }
+            [p, ..] => {
+                for (i, c) in text.iter().enumerate() {
+                    let matches = if *p == '?' { *c != '/' } else { c == p };
+                    next[i + 1] = reachable[i] && matches;
+                }
+                rest = &rest[1..];
+            }
+            [] => unreachable!("Checked the pattern wasn't empty"),
+        }
+        reachable = next;
+    }
+    reachable[text.len()]
+}
+
+/// Shows a [`FileSystem`] as a tree, see [`FileSystem::tree_display`]
+pub struct TreeDisplay<'a> {
+    filesystem: &'a FileSystem,
+    sizes: NodeValues<u64>,
+}
+
+impl TreeDisplay<'_> {
+    fn write_entry(&self, f: &mut Formatter<'_>, id: NodeId) -> std::fmt::Result {
+        let suffix = if self.filesystem.entry(id).is_directory() && id != self.filesystem.root() {
+            "/"
+        } else {
+            ""
+        };
+        writeln!(
+            f,
+            "{}{} ({})",
+            self.filesystem.name(id),
+            suffix,
+            human_size(self.sizes[id])
+        )
+    }
+
+    fn write_children(&self, f: &mut Formatter<'_>, id: NodeId, indent: &str) -> std::fmt::Result {
+        let children = self.filesystem.tree.children(id);
+        for (i, child) in children.iter().enumerate() {
+            let last = i == children.len() - 1;
+            write!(f, "{}{}", indent, if last { "└── " } else { "├── " })?;
+            self.write_entry(f, *child)?;
+            let indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+            self.write_children(f, *child, &indent)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TreeDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let root = self.filesystem.root();
+        self.write_entry(f, root)?;
+        self.write_children(f, root, "")
+    }
+}
+
+fn path(tree: &Tree<String, Entry>, id: NodeId) -> String {
+    let mut names: Vec<&str> = std::iter::once(id)
+        .chain(tree.ancestors(id))
+        .filter_map(|id| tree.key(id).map(String::as_str))
+        .collect();
+    names.reverse();
+    format!("/{}", names.join("/"))
+}
+
+/// The `ls` currently being read
+struct Listing {
+    directory: NodeId,
+    /// Whether this directory has been listed before, in which case the listing is checked
+    /// against what was seen the first time rather than added
+    repeated: bool,
+    seen: HashSet<String>,
+    line: String,
+    line_number: usize,
+}
+
+/// Rebuilds a [`FileSystem`] one line at a time from a terminal session that explores it with
+/// `cd` and `ls`
+///
+/// ```rust
+/// use advent_of_code_2022::domain::filesystem::Transcript;
+///
+/// let session = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\n\
+///     dir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n\
+///     4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+/// let mut transcript = Transcript::new();
+/// for line in session.lines() {
+///     transcript.replay(line).unwrap();
+/// }
+/// let fs = transcript.finish().unwrap();
+/// let sizes = fs.sizes();
+/// assert_eq!(sizes[fs.find("/a/e").unwrap()], 584);
+/// assert_eq!(sizes[fs.find("/a").unwrap()], 94853);
+/// assert_eq!(sizes[fs.root()], 48381165);
+/// ```
+///
+/// Listing a directory a second time must show exactly the same entries
+///
+/// ```rust
+/// use advent_of_code_2022::domain::filesystem::Transcript;
+///
+/// let mut transcript = Transcript::new();
+/// for line in ["$ ls", "10 a", "$ ls", "10 a"] {
+///     transcript.replay(line).unwrap();
+/// }
+/// let error = transcript.replay("20 b").unwrap_err();
+/// assert_eq!(error.line_number(), Some(5));
+///
+/// let mut transcript = Transcript::new();
+/// for line in ["$ ls", "10 a", "dir b", "$ ls", "10 a"] {
+///     transcript.replay(line).unwrap();
+/// }
+/// let error = transcript.finish().unwrap_err();
+/// assert_eq!(error.line(), "$ ls");
+/// assert_eq!(error.line_number(), Some(4));
+/// ```
+///
+/// and the first listing of a directory must include anything already entered with `cd`
+///
+/// ```rust
+/// use advent_of_code_2022::domain::filesystem::Transcript;
+///
+/// let error = Transcript::replay_all(["$ cd a", "$ cd /", "$ ls", "10 b"]).unwrap_err();
+/// assert_eq!(error.message(), "a is missing from this listing of /");
+/// assert_eq!(error.line_number(), Some(3));
+/// ```
+pub struct Transcript {
+    tree: Tree<String, Entry>,
+    current: NodeId,
+    listed: HashSet<NodeId>,
+    listing: Option<Listing>,
+    line_number: usize,
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        let tree = Tree::new(Entry::Directory);
+        Transcript {
+            current: tree.root(),
+            tree,
+            listed: HashSet::new(),
+            listing: None,
+            line_number: 0,
+        }
+    }
+}
+
+impl Transcript {
+    /// Starts a session in the root directory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replays a whole session
+    pub fn replay_all<I, S>(lines: I) -> Result<FileSystem, Diagnostic>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut transcript = Self::new();
+        for line in lines {
+            transcript.replay(line.as_ref())?;
+        }
+        transcript.finish()
+    }
+
+    /// Reads the next line of the session. Any error has the line number filled in
+    pub fn replay(&mut self, line: &str) -> Result<(), Diagnostic> {
+        self.line_number += 1;
+        let line_number = self.line_number;
+        TerminalLine::from_str(line)
+            .and_then(|terminal_line| self.apply(line, terminal_line))
+            .map_err(|d| match d.line_number() {
+                // A repeated listing that left something out points back at its `ls`
+                Some(_) => d,
+                None => d.with_line_number(line_number),
+            })
+    }
+
+    fn apply(&mut self, line: &str, terminal_line: TerminalLine) -> Result<(), Diagnostic> {
+        match terminal_line {
+            TerminalLine::Cd(target) => {
+                self.end_listing()?;
+                self.current = match target {
+                    CdTarget::Root => self.tree.root(),
+                    CdTarget::Parent => self.tree.parent(self.current).ok_or_else(|| {
+                        Diagnostic::new("Can't go above the root directory", line, 5..7)
+                    })?,
+                    CdTarget::Child(name) => self.enter(line, name)?,
+                };
+            }
+            TerminalLine::Ls => {
+                self.end_listing()?;
+                self.listing = Some(Listing {
+                    directory: self.current,
+                    repeated: !self.listed.insert(self.current),
+                    seen: HashSet::new(),
+                    line: line.to_string(),
+                    line_number: self.line_number,
+                });
+            }
+            TerminalLine::Dir(name) => self.listed_entry(line, name, Entry::Directory)?,
+            TerminalLine::File { size, name } => {
+                self.listed_entry(line, name, Entry::File(size))?
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves into a directory, creating it if its parent hasn't been listed yet
+    fn enter(&mut self, line: &str, name: String) -> Result<NodeId, Diagnostic> {
+        let span = 5..line.len();
+        match self.tree.child(self.current, &name) {
+            Some(child) if self.tree[child].is_directory() => Ok(child),
+            Some(_) => Err(Diagnostic::new(
+                format!("{} is a file, not a directory", name),
+                line,
+                span,
+            )),
+            None if self.listed.contains(&self.current) => Err(Diagnostic::new(
+                format!(
+                    "{} has no directory called {}",
+                    path(&self.tree, self.current),
+                    name
+                ),
+                line,
+                span,
+            )),
+            None => Ok(self
+                .tree
+                .add_child(self.current, name, Entry::Directory)
+                .expect("Child was not found but could not be added")),
+        }
+    }
+
+    fn listed_entry(&mut self, line: &str, name: String, entry: Entry) -> Result<(), Diagnostic> {
+        let listing = self
+            .listing
+            .as_mut()
+            .ok_or_else(|| Diagnostic::whole_line("Output without an ls command", line))?;
+        if !listing.seen.insert(name.clone()) {
+            return Err(Diagnostic::whole_line(
+                format!("{} was already listed", name),
+                line,
+            ));
+        }
+        let directory = listing.directory;
+        if listing.repeated {
+            let before = self.tree.child(directory, &name).map(|id| self.tree[id]);
+            if before != Some(entry) {
+                return Err(Diagnostic::whole_line(
+                    format!("{} was listed differently before", name),
+                    line,
+                ));
+            }
+            return Ok(());
+        }
+        match self.tree.add_child(directory, name, entry) {
+            // Entered with cd before it was listed
+            Err(existing) if self.tree[existing] == entry => Ok(()),
+            Err(_) => Err(Diagnostic::whole_line(
+                "Listed as a file, but was already entered as a directory",
+                line,
+            )),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Checks a listing didn't leave out anything already known to be in the directory, either
+    /// from an earlier listing or from using `cd` to go into it
+    fn end_listing(&mut self) -> Result<(), Diagnostic> {
+        let listing = match self.listing.take() {
+            Some(listing) => listing,
+            None => return Ok(()),
+        };
+        let missing = self
+            .tree
+            .children(listing.directory)
+            .iter()
+            .filter_map(|child| self.tree.key(*child))
+            .find(|name| !listing.seen.contains(*name));
+        if let Some(name) = missing {
+            return Err(Diagnostic::whole_line(
+                format!(
+                    "{} is missing from this listing of {}",
+                    name,
+                    path(&self.tree, listing.directory)
+                ),
+                &listing.line,
+            )
+            .with_line_number(listing.line_number));
+        }
+        Ok(())
+    }
+
+    /// Finishes the session, returning everything that was found
+    pub fn finish(mut self) -> Result<FileSystem, Diagnostic> {
+        self.end_listing()?;
+        Ok(FileSystem { tree: self.tree })
+    }
+}
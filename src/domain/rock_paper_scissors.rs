@@ -1,4 +1,6 @@
+use crate::input::diagnostic::Diagnostic;
 use std::convert::TryFrom;
+use std::ops::Range;
 
 #[derive(Copy, Clone, Debug)]
 pub enum GameResult {
@@ -109,44 +111,79 @@ pub struct RockPaperScissorsGame {
     mine: RockPaperScissors,
 }
 
+/// Splits a line into the two characters either side of the space, along with where they are
+fn split_game_str(s: &str) -> Result<[(char, Range<usize>); 2], Diagnostic> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    if chars.len() != 3 {
+        let start = chars.get(3).map(|(i, _)| *i).unwrap_or(0);
+        return Err(Diagnostic::new(
+            format!("Invalid string length, expected 3 got {}", chars.len()),
+            s,
+            start..s.len(),
+        ));
+    }
+    if chars[1].1 != ' ' {
+        return Err(Diagnostic::new(
+            "Invalid string, expected space in center",
+            s,
+            chars[1].0..chars[2].0,
+        ));
+    }
+    Ok([
+        (chars[0].1, 0..chars[1].0),
+        (chars[2].1, chars[2].0..s.len()),
+    ])
+}
+
 impl RockPaperScissorsGame {
-    pub fn from_play_str(s: &str) -> Result<Self, String> {
-        if s.chars().count() > 3 {
-            Err(format!(
-                "Invalid string length, expect 3 go {}: '{}'",
-                s.len(),
-                s
-            ))
-        } else if s.chars().nth(1) != Some(' ') {
-            Err(format!(
-                "Invalid string, expected space in center, got {}",
-                s
-            ))
-        } else {
-            let theirs = s.chars().next().unwrap().try_into()?;
-            let mine = s.chars().nth(2).unwrap().try_into()?;
-            Ok(Self { theirs, mine })
-        }
+    /// Reads a game where both sides are plays
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::rock_paper_scissors::RockPaperScissorsGame;
+    ///
+    /// # fn main() -> Result<(), String> {
+    /// let game = RockPaperScissorsGame::from_play_str("A Y")?;
+    /// assert_eq!(game.score(), 8);
+    ///
+    /// let error = RockPaperScissorsGame::from_play_str("A Q").err().unwrap();
+    /// assert_eq!(error.span(), 2..3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_play_str(s: &str) -> Result<Self, Diagnostic> {
+        let [(theirs, theirs_span), (mine, mine_span)] = split_game_str(s)?;
+        let theirs = theirs
+            .try_into()
+            .map_err(|e: String| Diagnostic::new(e, s, theirs_span))?;
+        let mine = mine
+            .try_into()
+            .map_err(|e: String| Diagnostic::new(e, s, mine_span))?;
+        Ok(Self { theirs, mine })
     }
 
-    pub fn from_results_str(s: &str) -> Result<Self, String> {
-        if s.chars().count() > 3 {
-            Err(format!(
-                "Invalid string length, expect 3 go {}: '{}'",
-                s.len(),
-                s
-            ))
-        } else if s.chars().nth(1) != Some(' ') {
-            Err(format!(
-                "Invalid string, expected space in center, got {}",
-                s
-            ))
-        } else {
-            let theirs = s.chars().next().unwrap().try_into()?;
-            let result_char = s.chars().nth(2).unwrap();
-            let mine = GameResult::from_result_char(result_char, theirs)?.play();
-            Ok(Self { theirs, mine })
-        }
+    /// Reads a game where the second character is the result we want
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::domain::rock_paper_scissors::RockPaperScissorsGame;
+    ///
+    /// # fn main() -> Result<(), String> {
+    /// let game = RockPaperScissorsGame::from_results_str("A Y")?;
+    /// assert_eq!(game.score(), 4);
+    ///
+    /// let error = RockPaperScissorsGame::from_results_str("A-Y").err().unwrap();
+    /// assert_eq!(error.span(), 1..2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_results_str(s: &str) -> Result<Self, Diagnostic> {
+        let [(theirs, theirs_span), (result_char, result_span)] = split_game_str(s)?;
+        let theirs = theirs
+            .try_into()
+            .map_err(|e: String| Diagnostic::new(e, s, theirs_span))?;
+        let mine = GameResult::from_result_char(result_char, theirs)
+            .map_err(|e| Diagnostic::new(e, s, result_span))?
+            .play();
+        Ok(Self { theirs, mine })
     }
 }
 
@@ -1,3 +1,5 @@
+use crate::data_structure::BitSet64;
+use crate::input::diagnostic::Diagnostic;
 use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Ord, Eq)]
@@ -37,7 +39,23 @@ impl From<char> for ItemType {
     }
 }
 
-/// Stores items of item type. Evenly splits items into two compartments
+/// Each item type is stored as the bit matching its priority
+///
+/// ```rust
+/// use advent_of_code_2022::data_structure::BitSet64;
+/// use advent_of_code_2022::domain::rucksack::ItemType;
+///
+/// let items: BitSet64 = "aAa".chars().map(ItemType::from).collect();
+/// assert_eq!(items.iter().collect::<Vec<_>>(), vec![1, 27]);
+/// ```
+impl FromIterator<ItemType> for BitSet64 {
+    fn from_iter<I: IntoIterator<Item = ItemType>>(iter: I) -> Self {
+        iter.into_iter().map(|item| item.priority()).collect()
+    }
+}
+
+/// Stores items of item type. Evenly splits items into two compartments, each of which is the set
+/// of item types in it
 ///
 /// ```rust
 /// use std::str::FromStr;
@@ -45,33 +63,28 @@ impl From<char> for ItemType {
 ///
 /// # fn main() -> Result<(), String> {
 /// let rucksack = Rucksack::from_str("abcd")?;
-/// assert_eq!(rucksack.left(), &['a'.into(), 'b'.into()]);
-/// assert_eq!(rucksack.right(), &['c'.into(), 'd'.into()]);
+/// assert_eq!(rucksack.left(), "ab".chars().map(ItemType::from).collect());
+/// assert_eq!(rucksack.right(), "cd".chars().map(ItemType::from).collect());
 /// # Ok(())
 /// # }
 /// ```
-pub struct Rucksack(Vec<ItemType>, Vec<ItemType>);
+pub struct Rucksack(BitSet64, BitSet64);
 
 impl Rucksack {
-    pub fn left(&self) -> &[ItemType] {
-        &self.0
+    pub fn left(&self) -> BitSet64 {
+        self.0
     }
 
-    pub fn right(&self) -> &[ItemType] {
-        &self.1
+    pub fn right(&self) -> BitSet64 {
+        self.1
     }
 
-    pub fn unique_items(&self) -> Vec<ItemType> {
-        let mut v = Vec::with_capacity(2 * self.left().len());
-        v.extend_from_slice(self.left());
-        v.extend_from_slice(self.right());
-        v.sort();
-        v.dedup();
-        v
+    pub fn unique_items(&self) -> BitSet64 {
+        self.left() | self.right()
     }
 
     pub fn contains(&self, i: &ItemType) -> bool {
-        self.left().contains(i) || self.right().contains(i)
+        self.unique_items().contains(i.priority())
     }
 
     /// Stores items of item type. Evenly splits items into two compartments
@@ -90,39 +103,47 @@ impl Rucksack {
     /// # }
     /// ```
     pub fn clashing_priority_value(&self) -> usize {
-        // TODO: Make less expensive
-        let mut temp = self.left().to_vec();
-        temp.sort();
-        temp.dedup();
-
-        temp.iter()
-            .filter_map(|t| {
-                if self.right().contains(t) {
-                    Some(t.priority())
-                } else {
-                    None
-                }
-            })
-            .sum()
+        (self.left() & self.right()).iter().sum()
     }
 }
 
 impl FromStr for Rucksack {
-    type Err = String;
+    type Err = Diagnostic;
 
+    /// Parses a rucksack, which must have the same number of items in each compartment. Every
+    /// item must be a letter
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use advent_of_code_2022::domain::rucksack::Rucksack;
+    ///
+    /// let error = Rucksack::from_str("abc").err().unwrap();
+    /// assert_eq!(error.span(), 0..3);
+    ///
+    /// let error = Rucksack::from_str("ab1!").err().unwrap();
+    /// assert_eq!(error.message(), "Invalid item");
+    /// assert_eq!(error.span(), 2..3);
+    ///
+    /// // 'é' takes two bytes, so the span covers both
+    /// let error = Rucksack::from_str("aéb").err().unwrap();
+    /// assert_eq!(error.span(), 1..3);
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() % 2 != 0 {
-            Err(format!(
-                "Rucksack should have even length, actually had {}",
-                s.len()
-            ))
-        } else {
-            let (c1, c2) = s.split_at(s.len() / 2);
-            Ok(Self(
-                c1.chars().map(|c| c.into()).collect::<Vec<_>>(),
-                c2.chars().map(|c| c.into()).collect::<Vec<_>>(),
-            ))
+        if let Some((i, c)) = s.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(Diagnostic::new("Invalid item", s, i..i + c.len_utf8()));
         }
+        let count = s.chars().count();
+        if !count.is_multiple_of(2) {
+            return Err(Diagnostic::whole_line(
+                format!("Rucksack should have even length, actually had {}", count),
+                s,
+            ));
+        }
+        let mut items = s.chars().map(ItemType::from);
+        Ok(Self(
+            items.by_ref().take(count / 2).collect(),
+            items.collect(),
+        ))
     }
 }
 
@@ -130,16 +151,14 @@ pub struct GroupRucksacks(Vec<Rucksack>);
 
 impl GroupRucksacks {
     pub fn find_badge(&self) -> ItemType {
-        let mut iter = self.0.iter();
-        let mut remaining_items = iter
-            .next()
-            .expect("Called find_badge on empty group")
-            .unique_items();
-        for next in iter {
-            remaining_items.retain(|i| next.contains(i));
-        }
+        let remaining_items = self
+            .0
+            .iter()
+            .map(Rucksack::unique_items)
+            .reduce(BitSet64::intersection)
+            .expect("Called find_badge on empty group");
         if remaining_items.len() == 1 {
-            remaining_items[0]
+            ItemType(remaining_items.iter().next().unwrap())
         } else {
             panic!("More than one item remained")
         }
@@ -1,7 +1,9 @@
-use std::ops::Range;
+use crate::data_structure::IntervalSet;
+use crate::input::diagnostic::Diagnostic;
+use crate::input::line_format::FromLine;
 use std::str::FromStr;
 
-pub struct Search(Range<usize>);
+pub struct Search(IntervalSet<usize>);
 
 impl Search {
     /// Can compares with another search area to see if this one completely contains the other
@@ -23,14 +25,10 @@ impl Search {
     /// # }
     /// ```
     pub fn contains_completely(&self, other: &Search) -> bool {
-        for i in other.0.clone() {
-            if !self.0.contains(&i) {
-                return false;
-            }
-        }
-        true
+        other.0.iter().all(|range| self.0.contains_range(&range))
     }
-    /// Can compares with another search area to see if this one completely contains the other
+
+    /// Can compares with another search area to see if they share any sections
     ///
     /// ```rust
     /// use std::str::FromStr;
@@ -45,35 +43,60 @@ impl Search {
     /// # }
     /// ```
     pub fn overlaps(&self, other: &Search) -> bool {
-        for i in other.0.clone() {
-            if self.0.contains(&i) {
-                return true;
-            }
-        }
-        false
+        other.0.iter().any(|range| self.0.overlaps(&range))
     }
 }
 
 impl FromStr for Search {
-    type Err = String;
+    type Err = Diagnostic;
 
+    /// Parses a search area in the form `start-end`
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use advent_of_code_2022::domain::search_party::Search;
+    ///
+    /// let error = Search::from_str("3-x").err().unwrap();
+    /// assert_eq!(error.span(), 2..3);
+    ///
+    /// // An area can't end before it starts
+    /// let error = Search::from_str("7-3").err().unwrap();
+    /// assert_eq!(error.span(), 0..3);
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split('-');
-        let start = split
-            .next()
-            .ok_or_else(|| format!("Invalid input for search {}", s))?
+        let (start, end) = s.split_once('-').ok_or_else(|| {
+            Diagnostic::whole_line("Invalid input for search, expected 'start-end'", s)
+        })?;
+        let start = start
             .parse::<usize>()
-            .map_err(|_| format!("Invalid input for search {}", s))?;
-        let end = split
-            .next()
-            .ok_or_else(|| format!("Invalid input for search {}", s))?
+            .map_err(|_| Diagnostic::new("Search start is not a number", s, 0..start.len()))?;
+        let end_offset = s.len() - end.len();
+        let end = end
             .parse::<usize>()
-            .map_err(|_| format!("Invalid input for search {}", s))?
+            .map_err(|_| Diagnostic::new("Search end is not a number", s, end_offset..s.len()))?
             + 1;
-        Ok(Self(Range { start, end }))
+        if end <= start {
+            return Err(Diagnostic::whole_line(
+                "Search area ends before it starts",
+                s,
+            ));
+        }
+        Ok(Self(IntervalSet::from(start..end)))
     }
 }
 
+/// A pair of search areas separated by a comma
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use advent_of_code_2022::domain::search_party::SearchPair;
+///
+/// let error = SearchPair::from_str("1-2,5-x").err().unwrap();
+/// assert_eq!(error.line(), "1-2,5-x");
+/// assert_eq!(error.span(), 6..7);
+/// ```
+#[derive(FromLine)]
+#[line("{0},{1}")]
 pub struct SearchPair(Search, Search);
 
 impl SearchPair {
@@ -115,18 +138,3 @@ impl SearchPair {
         self.0.overlaps(&self.1)
     }
 }
-
-impl FromStr for SearchPair {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(',');
-        let left = split
-            .next()
-            .ok_or_else(|| format!("Invalid input for group {}", s))?;
-        let right = split
-            .next()
-            .ok_or_else(|| format!("Invalid input for group {}", s))?;
-        Ok(Self(Search::from_str(left)?, Search::from_str(right)?))
-    }
-}
@@ -1,3 +1,10 @@
+pub mod byte_stream;
+pub mod compressed;
+pub mod diagnostic;
+pub mod fixed_width;
 pub mod grouped_input;
 pub mod grouped_input_raw;
+pub mod line_format;
+pub mod normalised;
+pub mod numbers;
 pub mod string_iter;
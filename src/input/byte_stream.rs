@@ -0,0 +1,80 @@
+use std::io::BufRead;
+
+/// Takes a buffer and returns the first line one byte at a time without reading the whole line
+/// into memory
+pub struct ByteStream<R: BufRead> {
+    read: R,
+    finished: bool,
+}
+
+/// Converts any BufRead to a Byte Stream.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use advent_of_code_2022::input::byte_stream::ByteStream;
+///
+/// let raw = Cursor::new("abc");
+/// let mut input = ByteStream::from(raw);
+/// ```
+impl<R: BufRead> From<R> for ByteStream<R> {
+    fn from(read: R) -> Self {
+        ByteStream {
+            read,
+            finished: false,
+        }
+    }
+}
+
+impl<R: BufRead> ByteStream<R> {
+    /// Treats each byte in the stream as an ASCII character
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use advent_of_code_2022::input::byte_stream::ByteStream;
+    ///
+    /// let raw = Cursor::new("abc\n");
+    /// let input = ByteStream::from(raw);
+    /// assert_eq!(input.chars().collect::<String>(), "abc");
+    /// ```
+    pub fn chars(self) -> impl Iterator<Item = char> {
+        self.map(char::from)
+    }
+}
+
+/// Reads the buffer one byte at a time, stopping at the end of the first line
+///
+/// ```rust
+/// use std::io::{BufReader, Cursor, Read};
+/// use advent_of_code_2022::input::byte_stream::ByteStream;
+///
+/// let raw = Cursor::new("ab\r\ncd");
+/// let mut input = ByteStream::from(raw);
+/// assert_eq!(input.next(), Some(b'a'));
+/// assert_eq!(input.next(), Some(b'b'));
+/// assert_eq!(input.next(), None);
+///
+/// // Streams are read a buffer at a time, so they don't need to fit in memory
+/// let raw = BufReader::with_capacity(1024, std::io::repeat(b'x').take(5_000));
+/// assert_eq!(ByteStream::from(raw).count(), 5_000);
+/// ```
+impl<R: BufRead> Iterator for ByteStream<R> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let byte = *self
+            .read
+            .fill_buf()
+            .expect("Failed to read from buffer")
+            .first()?;
+        self.read.consume(1);
+        if byte == b'\n' || byte == b'\r' {
+            self.finished = true;
+            None
+        } else {
+            Some(byte)
+        }
+    }
+}
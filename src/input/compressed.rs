@@ -0,0 +1,116 @@
+use flate2::bufread::MultiGzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::io::{BufRead, BufReader, Cursor, Error, ErrorKind, Read};
+use std::path::Path;
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+/// Enough bytes to recognise any of the magic numbers
+const MAGIC_LEN: usize = 4;
+
+/// The ways an input might be compressed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Works out how an input is compressed from its first few bytes. The file extension is only
+    /// used when there are too few bytes to tell, never to overrule them
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    /// use advent_of_code_2022::input::compressed::Compression;
+    ///
+    /// assert_eq!(Compression::detect(&[0x1F, 0x8B, 0x08], None), Compression::Gzip);
+    /// assert_eq!(Compression::detect(&[0x28, 0xB5, 0x2F, 0xFD], None), Compression::Zstd);
+    /// assert_eq!(Compression::detect(b"move", None), Compression::None);
+    /// assert_eq!(Compression::detect(b"", Some(Path::new("d05.txt.zst"))), Compression::Zstd);
+    /// assert_eq!(Compression::detect(b"move", Some(Path::new("d05.txt.gz"))), Compression::None);
+    /// ```
+    pub fn detect(start: &[u8], path: Option<&Path>) -> Self {
+        if start.starts_with(GZIP_MAGIC) {
+            return Compression::Gzip;
+        }
+        if start.starts_with(ZSTD_MAGIC) {
+            return Compression::Zstd;
+        }
+        let inconclusive = GZIP_MAGIC.starts_with(start) || ZSTD_MAGIC.starts_with(start);
+        if !inconclusive {
+            return Compression::None;
+        }
+        match path
+            .and_then(|path| path.extension())
+            .and_then(|extension| extension.to_str())
+        {
+            Some("gz") => Compression::Gzip,
+            Some("zst") | Some("zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// Wraps a buffer so that compressed input is decompressed as it's read. Uncompressed input is
+/// passed through untouched. `path` is only used to check the file extension.
+///
+/// ```rust
+/// use std::io::{BufRead, BufReader, Cursor, Write};
+/// use flate2::write::GzEncoder;
+/// use advent_of_code_2022::input::compressed::decompressed;
+///
+/// let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+/// encoder.write_all(b"123\n456\n").unwrap();
+/// let gzipped = Cursor::new(encoder.finish().unwrap());
+/// let lines: Vec<_> = decompressed(gzipped, None).unwrap().lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, vec!["123", "456"]);
+///
+/// let zstd = ruzstd::encoding::compress_to_vec(
+///     &b"789\n"[..],
+///     ruzstd::encoding::CompressionLevel::Fastest,
+/// );
+/// let lines: Vec<_> = decompressed(Cursor::new(zstd), None).unwrap().lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, vec!["789"]);
+///
+/// // The magic number is found even if the buffer gives back one byte at a time
+/// let zstd = ruzstd::encoding::compress_to_vec(
+///     &b"012\n"[..],
+///     ruzstd::encoding::CompressionLevel::Fastest,
+/// );
+/// let trickle = BufReader::with_capacity(1, Cursor::new(zstd));
+/// let lines: Vec<_> = decompressed(trickle, None).unwrap().lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, vec!["012"]);
+///
+/// let plain = Cursor::new("abc\n");
+/// let lines: Vec<_> = decompressed(plain, None).unwrap().lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, vec!["abc"]);
+/// ```
+pub fn decompressed<'a, R: BufRead + 'a>(
+    mut read: R,
+    path: Option<&Path>,
+) -> std::io::Result<Box<dyn BufRead + 'a>> {
+    // A single fill_buf can legally return fewer bytes than a magic number, so keep going until
+    // there are enough or the input ends
+    let mut start = Vec::with_capacity(MAGIC_LEN);
+    while start.len() < MAGIC_LEN {
+        let buffer = read.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+        let taken = buffer.len().min(MAGIC_LEN - start.len());
+        start.extend_from_slice(&buffer[..taken]);
+        read.consume(taken);
+    }
+    let compression = Compression::detect(&start, path);
+    let read = Cursor::new(start).chain(read);
+    Ok(match compression {
+        Compression::None => Box::new(read),
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(read))),
+        Compression::Zstd => {
+            let decoder =
+                StreamingDecoder::new(read).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            Box::new(BufReader::new(decoder))
+        }
+    })
+}
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Describes a problem with a line of input, pointing to the part of the line that was wrong
+///
+/// ```rust
+/// use advent_of_code_2022::input::diagnostic::Diagnostic;
+///
+/// let diagnostic = Diagnostic::new("Expected a number", "move x from 2 to 3", 5..6)
+///     .with_line_number(12)
+///     .with_file("d05.txt");
+///
+/// assert_eq!(diagnostic.render(false), "\
+/// error: Expected a number
+///   --> d05.txt:12:6
+///    |
+/// 12 | move x from 2 to 3
+///    |      ^
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    message: String,
+    line: String,
+    span: Range<usize>,
+    line_number: Option<usize>,
+    file: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for a line, where `span` is the byte range of the problem in the line.
+    /// An empty span points at the position just before its start. The span is clamped to the
+    /// line and widened to whole characters
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::input::diagnostic::Diagnostic;
+    ///
+    /// // 'é' takes two bytes
+    /// let diagnostic = Diagnostic::new("Not an item", "éa1", 3..4);
+    /// assert_eq!(diagnostic.render(false), "\
+    /// error: Not an item
+    ///  |
+    ///  | éa1
+    ///  |   ^
+    /// ");
+    /// assert_eq!(Diagnostic::new("Half a character", "éa1", 1..1).span(), 0..2);
+    /// assert_eq!(Diagnostic::new("Past the end", "éa1", 7..9).span(), 4..4);
+    /// ```
+    pub fn new<M: Into<String>>(message: M, line: &str, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            line: line.to_string(),
+            span: clamp_span(line, span),
+            line_number: None,
+            file: None,
+        }
+    }
+
+    /// Creates a diagnostic that points at the whole line
+    pub fn whole_line<M: Into<String>>(message: M, line: &str) -> Self {
+        Self::new(message, line, 0..line.len())
+    }
+
+    /// Sets the line number (starting at 1) the problem was found on
+    pub fn with_line_number(mut self, line_number: usize) -> Self {
+        self.line_number = Some(line_number);
+        self
+    }
+
+    /// Sets the name of the file the problem was found in
+    pub fn with_file<F: Into<String>>(mut self, file: F) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Moves a diagnostic for part of a line into the line that part came from, where `offset` is
+    /// the position the part started at
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::input::diagnostic::Diagnostic;
+    ///
+    /// let diagnostic = Diagnostic::new("Bad end", "5-x", 2..3).within("1-2,5-x", 4);
+    /// assert_eq!(diagnostic.span(), 6..7);
+    /// assert_eq!(diagnostic.line(), "1-2,5-x");
+    ///
+    /// let diagnostic = Diagnostic::new("Bad end", "5-x", 2..3).within("1-2", 4);
+    /// assert_eq!(diagnostic.span(), 3..3);
+    /// ```
+    pub fn within(self, line: &str, offset: usize) -> Self {
+        let span = self.span.start.saturating_add(offset)..self.span.end.saturating_add(offset);
+        Self {
+            line: line.to_string(),
+            span: clamp_span(line, span),
+            ..self
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn line_number(&self) -> Option<usize> {
+        self.line_number
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Renders the diagnostic in the style of rustc, optionally using ANSI colours
+    ///
+    /// ```rust
+    /// use advent_of_code_2022::input::diagnostic::Diagnostic;
+    ///
+    /// let diagnostic = Diagnostic::new("Not an item", "abc1", 3..4);
+    /// assert_eq!(diagnostic.render(false), "\
+    /// error: Not an item
+    ///  |
+    ///  | abc1
+    ///  |    ^
+    /// ");
+    /// ```
+    pub fn render(&self, colour: bool) -> String {
+        let (red, blue, bold, reset) = if colour {
+            (RED, BLUE, BOLD, RESET)
+        } else {
+            ("", "", "", "")
+        };
+        let number = self.line_number.map(|n| n.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(number.len());
+        let column = self.line[..self.span.start].chars().count() + 1;
+        let width = self.line[self.span.clone()].chars().count().max(1);
+
+        let mut output = format!("{}error{}{}: {}{}\n", red, reset, bold, self.message, reset);
+        match (&self.file, self.line_number) {
+            (Some(file), Some(n)) => output.push_str(&format!(
+                "{}{}-->{} {}:{}:{}\n",
+                gutter, blue, reset, file, n, column
+            )),
+            (Some(file), None) => {
+                output.push_str(&format!("{}{}-->{} {}\n", gutter, blue, reset, file))
+            }
+            (None, _) => {}
+        }
+        output.push_str(&format!("{} {}|{}\n", gutter, blue, reset));
+        output.push_str(&format!("{}{} |{} {}\n", blue, number, reset, self.line));
+        output.push_str(&format!(
+            "{} {}|{} {}{}{}{}\n",
+            gutter,
+            blue,
+            reset,
+            " ".repeat(column - 1),
+            red,
+            "^".repeat(width),
+            reset
+        ));
+        output
+    }
+}
+
+/// Keeps a span inside a line and on character boundaries, so the line can be sliced with it.
+/// The start is moved back and the end forward to cover any character they were part of
+fn clamp_span(line: &str, span: Range<usize>) -> Range<usize> {
+    let mut end = span.end.min(line.len());
+    while !line.is_char_boundary(end) {
+        end += 1;
+    }
+    let mut start = span.start.min(end);
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+    start..end
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+impl Error for Diagnostic {}
+
+impl From<Diagnostic> for String {
+    fn from(diagnostic: Diagnostic) -> Self {
+        diagnostic.to_string()
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+/// A single column in a fixed width table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub label: String,
+    pub span: Range<usize>,
+}
+
+/// Breaks rows of text into columns based on the position of labels in a header (or footer) row.
+///
+/// Each column starts half way between its label and the previous label, and ends half way
+/// between its label and the next one. The first column starts at the beginning of the line and
+/// the last column runs to the end of it. This means cells can be wider than their label and
+/// labels can be more than one character long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthTable {
+    columns: Vec<Column>,
+}
+
+impl FixedWidthTable {
+    /// The number of columns in the table
+    /// ```rust
+    /// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+    ///
+    /// let table = FixedWidthTable::from(" 1   2   3 ");
+    /// assert_eq!(table.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Tells you if the label row had no labels in it
+    /// ```rust
+    /// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+    ///
+    /// assert_eq!(FixedWidthTable::from("   ").is_empty(), true);
+    /// assert_eq!(FixedWidthTable::from(" 1 ").is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Gets the columns found in the label row
+    /// ```rust
+    /// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+    ///
+    /// let table = FixedWidthTable::from(" 1   2   3 ");
+    /// let spans: Vec<_> = table.columns().iter().map(|c| c.span.clone()).collect();
+    /// assert_eq!(spans, vec![0..3, 3..7, 7..usize::MAX]);
+    /// ```
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Gets the labels of each column in order
+    /// ```rust
+    /// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+    ///
+    /// let table = FixedWidthTable::from("  id   name  ");
+    /// assert_eq!(table.labels().collect::<Vec<_>>(), vec!["id", "name"]);
+    /// ```
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|column| column.label.as_str())
+    }
+
+    /// Splits a row into one cell per column. Cells are trimmed, and blank or missing cells are
+    /// returned as `None`
+    /// ```rust
+    /// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+    ///
+    /// let table = FixedWidthTable::from(" 1   2   3 ");
+    /// assert_eq!(table.cells("    [D]"), vec![None, Some("[D]"), None]);
+    /// assert_eq!(table.cells("[Z] [M] [P]"), vec![Some("[Z]"), Some("[M]"), Some("[P]")]);
+    ///
+    /// // Cells can be wider than their labels
+    /// let table = FixedWidthTable::from("  1    2  ");
+    /// assert_eq!(table.cells("[AB]      "), vec![Some("[AB]"), None]);
+    /// assert_eq!(table.cells("     [CD] "), vec![None, Some("[CD]")]);
+    /// ```
+    pub fn cells<'a>(&self, row: &'a str) -> Vec<Option<&'a str>> {
+        self.columns
+            .iter()
+            .map(|column| {
+                let cell = slice_chars(row, &column.span).trim();
+                if cell.is_empty() {
+                    None
+                } else {
+                    Some(cell)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Slices a string by character positions, clamping the range to the length of the string
+fn slice_chars<'a>(s: &'a str, span: &Range<usize>) -> &'a str {
+    let byte_index = |position: usize| {
+        s.char_indices()
+            .nth(position)
+            .map(|(i, _)| i)
+            .unwrap_or(s.len())
+    };
+    let start = byte_index(span.start);
+    let end = byte_index(span.end);
+    &s[start..end]
+}
+
+/// Finds the columns in a row of labels. Labels are separated by whitespace
+///
+/// ```rust
+/// use advent_of_code_2022::input::fixed_width::FixedWidthTable;
+///
+/// // Works with more than 9 columns
+/// let footer = " 1   2   3   4   5   6   7   8   9   10  11 ";
+/// let table = FixedWidthTable::from(footer);
+/// assert_eq!(table.len(), 11);
+/// assert_eq!(table.labels().last(), Some("11"));
+/// let row = "[A] [B] [C] [D] [E] [F] [G] [H] [I] [J] [K]";
+/// assert_eq!(table.cells(row)[9], Some("[J]"));
+/// assert_eq!(table.cells(row)[10], Some("[K]"));
+/// ```
+impl From<&str> for FixedWidthTable {
+    fn from(label_row: &str) -> Self {
+        let mut labels: Vec<(String, Range<usize>)> = Vec::new();
+        for (position, c) in label_row.chars().enumerate() {
+            if c.is_whitespace() {
+                continue;
+            }
+            match labels.last_mut() {
+                Some((label, span)) if span.end == position => {
+                    label.push(c);
+                    span.end += 1;
+                }
+                _ => labels.push((c.to_string(), position..position + 1)),
+            }
+        }
+
+        let boundaries: Vec<usize> = labels
+            .windows(2)
+            .map(|pair| (pair[0].1.end + pair[1].1.start) / 2)
+            .collect();
+
+        let columns = labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, _))| {
+                let start = if i == 0 { 0 } else { boundaries[i - 1] };
+                let end = boundaries.get(i).copied().unwrap_or(usize::MAX);
+                Column {
+                    label,
+                    span: start..end,
+                }
+            })
+            .collect();
+
+        Self { columns }
+    }
+}
@@ -0,0 +1,191 @@
+//! Support for `#[derive(FromLine)]`, which implements `FromStr` for a type from a format string.
+//!
+//! Fields are written as `{name}` (or `{0}` for tuple structs) and everything else has to match
+//! exactly. A field runs until the text that follows it in the format, or to the end of the line
+//! if it is last. Use `{{` and `}}` for literal braces. Each field is parsed with its own `FromStr`,
+//! so types that derive `FromLine` can be nested inside each other. Whitespace at the start and
+//! end of the line is ignored.
+//!
+//! ```rust
+//! use std::str::FromStr;
+//! use advent_of_code_2022::input::line_format::FromLine;
+//!
+//! #[derive(Debug, PartialEq, FromLine)]
+//! #[line("{0}-{1}")]
+//! struct Range(usize, usize);
+//!
+//! #[derive(Debug, PartialEq, FromLine)]
+//! #[line("{left},{right}")]
+//! struct Pair {
+//!     left: Range,
+//!     right: Range,
+//! }
+//!
+//! # fn main() -> Result<(), String> {
+//! let pair = Pair::from_str("2-4,6-8")?;
+//! assert_eq!(pair, Pair { left: Range(2, 4), right: Range(6, 8) });
+//!
+//! // Errors point at the part of the line that was wrong, even in nested types
+//! let error = Pair::from_str("2-4,6-x").unwrap_err();
+//! assert_eq!(error.span(), 6..7);
+//! assert_eq!(error.message(), "Invalid value for 1: invalid digit found in string");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every field has to appear in the format exactly once
+//!
+//! ```compile_fail
+//! use advent_of_code_2022::input::line_format::FromLine;
+//!
+//! #[derive(FromLine)]
+//! #[line("{a}")]
+//! struct Missing {
+//!     a: usize,
+//!     b: usize,
+//! }
+//! ```
+//!
+//! and every name in the format has to be a field
+//!
+//! ```compile_fail
+//! use advent_of_code_2022::input::line_format::FromLine;
+//!
+//! #[derive(FromLine)]
+//! #[line("{a},{c}")]
+//! struct Unknown {
+//!     a: usize,
+//!     b: usize,
+//! }
+//! ```
+//!
+//! A malformed format is a compile error too
+//!
+//! ```compile_fail
+//! use advent_of_code_2022::input::line_format::FromLine;
+//!
+//! #[derive(FromLine)]
+//! #[line("{a}{b}")]
+//! struct Adjacent {
+//!     a: usize,
+//!     b: usize,
+//! }
+//! ```
+
+use crate::input::diagnostic::Diagnostic;
+use std::char::ParseCharError;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::num::{ParseFloatError, ParseIntError};
+use std::ops::Range;
+use std::str::{FromStr, ParseBoolError};
+
+pub use line_format_derive::FromLine;
+
+/// Errors from parsing a field that can be turned into a diagnostic for the whole line
+pub trait IntoDiagnostic {
+    /// Creates a diagnostic for `line`, where the field that failed was at `span`
+    fn into_diagnostic(self, field: &str, line: &str, span: Range<usize>) -> Diagnostic;
+}
+
+impl IntoDiagnostic for Diagnostic {
+    fn into_diagnostic(self, _field: &str, line: &str, span: Range<usize>) -> Diagnostic {
+        self.within(line, span.start)
+    }
+}
+
+fn describe<E: Display>(error: E, field: &str, line: &str, span: Range<usize>) -> Diagnostic {
+    Diagnostic::new(
+        format!("Invalid value for {}: {}", field, error),
+        line,
+        span,
+    )
+}
+
+macro_rules! impl_into_diagnostic {
+    ($($t:ty),+) => {
+        $(
+            impl IntoDiagnostic for $t {
+                fn into_diagnostic(self, field: &str, line: &str, span: Range<usize>) -> Diagnostic {
+                    describe(self, field, line, span)
+                }
+            }
+        )+
+    };
+}
+
+impl_into_diagnostic!(
+    String,
+    Infallible,
+    ParseIntError,
+    ParseFloatError,
+    ParseBoolError,
+    ParseCharError
+);
+
+/// Checks `literal` is next in the line and moves past it
+pub fn expect_literal(line: &str, position: &mut usize, literal: &str) -> Result<(), Diagnostic> {
+    if line[*position..].starts_with(literal) {
+        *position += literal.len();
+        Ok(())
+    } else {
+        let found = line[*position..]
+            .char_indices()
+            .nth(literal.chars().count())
+            .map(|(i, _)| *position + i)
+            .unwrap_or(line.len());
+        Err(Diagnostic::new(
+            format!("Expected '{}'", literal),
+            line,
+            *position..found,
+        ))
+    }
+}
+
+/// Finds the text for a field, which runs until the `next` literal, or the end of the line
+pub fn take_field(
+    line: &str,
+    position: &mut usize,
+    next: Option<&str>,
+) -> Result<Range<usize>, Diagnostic> {
+    let start = *position;
+    let end = match next {
+        Some(literal) => line[start..]
+            .find(literal)
+            .map(|i| start + i)
+            .ok_or_else(|| {
+                Diagnostic::new(
+                    format!("Expected '{}'", literal),
+                    line,
+                    line.len()..line.len(),
+                )
+            })?,
+        None => line.len(),
+    };
+    *position = end;
+    Ok(start..end)
+}
+
+/// Checks nothing is left over at the end of the line
+pub fn expect_end(line: &str, position: usize) -> Result<(), Diagnostic> {
+    if position == line.len() {
+        Ok(())
+    } else {
+        Err(Diagnostic::new(
+            "Unexpected text at end of line",
+            line,
+            position..line.len(),
+        ))
+    }
+}
+
+/// Parses the text for a field
+pub fn parse_field<T>(line: &str, span: Range<usize>, field: &str) -> Result<T, Diagnostic>
+where
+    T: FromStr,
+    T::Err: IntoDiagnostic,
+{
+    line[span.clone()]
+        .parse()
+        .map_err(|e: T::Err| e.into_diagnostic(field, line, span))
+}
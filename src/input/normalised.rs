@@ -0,0 +1,166 @@
+use std::io::{BufRead, Read};
+
+const BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Wraps a buffer so that inputs saved on different systems read the same way. A UTF-8 byte order
+/// mark at the start is removed, and `\r\n` line endings become `\n`. Trailing whitespace can also
+/// be removed from each line, while leaving leading indentation alone.
+///
+/// The input is processed as it's read, so long lines don't have to fit in memory.
+pub struct Normalised<R: BufRead> {
+    read: R,
+    state: State,
+}
+
+/// Everything but the underlying buffer, so bytes can be normalised straight out of that buffer
+/// into `output`, which is reused for every chunk
+struct State {
+    trim_trailing_whitespace: bool,
+    bom_matched: Option<usize>,
+    pending_cr: bool,
+    pending_whitespace: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+}
+
+/// Converts any BufRead to a Normalised buffer.
+///
+/// ```rust
+/// use std::io::{Cursor, Read};
+/// use advent_of_code_2022::input::normalised::Normalised;
+///
+/// let raw = Cursor::new("\u{feff}123\r\n456\r\n");
+/// let mut output = String::new();
+/// Normalised::from(raw).read_to_string(&mut output).unwrap();
+/// assert_eq!(output, "123\n456\n");
+/// ```
+impl<R: BufRead> From<R> for Normalised<R> {
+    fn from(read: R) -> Self {
+        Normalised {
+            read,
+            state: State {
+                trim_trailing_whitespace: false,
+                bom_matched: Some(0),
+                pending_cr: false,
+                pending_whitespace: Vec::new(),
+                output: Vec::new(),
+                position: 0,
+            },
+        }
+    }
+}
+
+impl<R: BufRead> Normalised<R> {
+    /// Also removes spaces and tabs from the end of each line
+    ///
+    /// ```rust
+    /// use std::io::{Cursor, Read};
+    /// use advent_of_code_2022::input::normalised::Normalised;
+    ///
+    /// let raw = Cursor::new("    [D]    \r\n[N] [C]  \n   \n 1   2 ");
+    /// let mut output = String::new();
+    /// Normalised::from(raw)
+    ///     .trim_trailing_whitespace()
+    ///     .read_to_string(&mut output)
+    ///     .unwrap();
+    /// assert_eq!(output, "    [D]\n[N] [C]\n\n 1   2");
+    /// ```
+    pub fn trim_trailing_whitespace(mut self) -> Self {
+        self.state.trim_trailing_whitespace = true;
+        self
+    }
+}
+
+impl State {
+    /// Adds a byte of line content to the output
+    fn push_content(&mut self, byte: u8) {
+        if self.trim_trailing_whitespace && (byte == b' ' || byte == b'\t') {
+            self.pending_whitespace.push(byte);
+        } else {
+            self.output.append(&mut self.pending_whitespace);
+            self.output.push(byte);
+        }
+    }
+
+    fn push_newline(&mut self) {
+        self.pending_whitespace.clear();
+        self.output.push(b'\n');
+    }
+
+    fn push(&mut self, byte: u8) {
+        if let Some(matched) = self.bom_matched {
+            if byte == BOM[matched] {
+                self.bom_matched = (matched + 1 < BOM.len()).then_some(matched + 1);
+                return;
+            }
+            // Not a BOM after all, so keep whatever looked like the start of one
+            self.bom_matched = None;
+            for &b in &BOM[..matched] {
+                self.push(b);
+            }
+        }
+
+        if self.pending_cr {
+            self.pending_cr = false;
+            if byte == b'\n' {
+                self.push_newline();
+                return;
+            }
+            self.push_content(b'\r');
+        }
+
+        match byte {
+            b'\r' => self.pending_cr = true,
+            b'\n' => self.push_newline(),
+            _ => self.push_content(byte),
+        }
+    }
+
+    /// Deals with anything held back once the underlying buffer is empty
+    fn finish(&mut self) {
+        if let Some(matched) = self.bom_matched.take() {
+            self.output.extend_from_slice(&BOM[..matched]);
+        }
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.push_content(b'\r');
+        }
+        self.pending_whitespace.clear();
+    }
+}
+
+impl<R: BufRead> Read for Normalised<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amount = available.len().min(buf.len());
+        buf[..amount].copy_from_slice(&available[..amount]);
+        self.consume(amount);
+        Ok(amount)
+    }
+}
+
+impl<R: BufRead> BufRead for Normalised<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        let state = &mut self.state;
+        while state.position == state.output.len() {
+            state.output.clear();
+            state.position = 0;
+            let chunk = self.read.fill_buf()?;
+            if chunk.is_empty() {
+                state.finish();
+                break;
+            }
+            let amount = chunk.len();
+            for &byte in chunk {
+                state.push(byte);
+            }
+            self.read.consume(amount);
+        }
+        Ok(&state.output[state.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        let state = &mut self.state;
+        state.position = (state.position + amount).min(state.output.len());
+    }
+}
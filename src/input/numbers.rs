@@ -0,0 +1,165 @@
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// Iterates over every signed integer in a line of text
+pub struct Numbers<'a> {
+    line: &'a str,
+    position: usize,
+}
+
+/// Finds all the integers in a line, ignoring any other text.
+///
+/// A `-` is only treated as a sign if it does not directly follow a letter or digit, so ranges such
+/// as `2-4` are read as two positive numbers.
+///
+/// Numbers too large for an `i64` are skipped, use [`extract`] to treat them as an error.
+///
+/// ```rust
+/// use advent_of_code_2022::input::numbers::numbers;
+///
+/// let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+/// assert_eq!(numbers(line).collect::<Vec<_>>(), vec![2, 18, -2, 15]);
+///
+/// assert_eq!(numbers("2-4,6-8").collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+/// assert_eq!(numbers("no numbers here").next(), None);
+/// assert_eq!(numbers("id 123456789012345678901234567890 is 7").collect::<Vec<_>>(), vec![7]);
+/// ```
+pub fn numbers(line: &str) -> Numbers<'_> {
+    Numbers { line, position: 0 }
+}
+
+impl<'a> Numbers<'a> {
+    /// Gets the position of the next number in the line without parsing it
+    fn next_span(&mut self) -> Option<Range<usize>> {
+        let bytes = self.line.as_bytes();
+        let mut start = self.position;
+        while start < bytes.len() && !bytes[start].is_ascii_digit() {
+            start += 1;
+        }
+        if start == bytes.len() {
+            self.position = start;
+            return None;
+        }
+        let mut end = start;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        self.position = end;
+
+        let is_sign = start > 0
+            && bytes[start - 1] == b'-'
+            && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric());
+        if is_sign {
+            start -= 1;
+        }
+        Some(start..end)
+    }
+
+    /// Gets the text of the next number in the line
+    fn next_str(&mut self) -> Option<&'a str> {
+        let line = self.line;
+        self.next_span().map(|span| &line[span])
+    }
+}
+
+impl<'a> Iterator for Numbers<'a> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Runs of digits too long for an i64 aren't numbers this can give back, so skip them
+        while let Some(s) = self.next_str() {
+            if let Ok(n) = s.parse() {
+                return Some(n);
+            }
+        }
+        None
+    }
+}
+
+/// Types that can be built from a fixed amount of numbers
+pub trait FromNumbers: Sized {
+    fn from_numbers(numbers: &[i64]) -> Result<Self, String>;
+}
+
+fn convert<T: TryFrom<i64>>(n: i64) -> Result<T, String> {
+    T::try_from(n).map_err(|_| format!("{} is out of range", n))
+}
+
+fn check_count(numbers: &[i64], expected: usize) -> Result<(), String> {
+    if numbers.len() == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "Expected {} numbers, found {}",
+            expected,
+            numbers.len()
+        ))
+    }
+}
+
+macro_rules! impl_from_numbers_for_tuple {
+    ($count:expr => $($t:ident : $i:tt),+) => {
+        impl<$($t: TryFrom<i64>),+> FromNumbers for ($($t,)+) {
+            fn from_numbers(numbers: &[i64]) -> Result<Self, String> {
+                check_count(numbers, $count)?;
+                Ok(($(convert::<$t>(numbers[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_numbers_for_tuple!(1 => A: 0);
+impl_from_numbers_for_tuple!(2 => A: 0, B: 1);
+impl_from_numbers_for_tuple!(3 => A: 0, B: 1, C: 2);
+impl_from_numbers_for_tuple!(4 => A: 0, B: 1, C: 2, D: 3);
+impl_from_numbers_for_tuple!(5 => A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_from_numbers_for_tuple!(6 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_from_numbers_for_tuple!(7 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_from_numbers_for_tuple!(8 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+impl<T: TryFrom<i64>, const N: usize> FromNumbers for [T; N] {
+    fn from_numbers(numbers: &[i64]) -> Result<Self, String> {
+        check_count(numbers, N)?;
+        let converted = numbers
+            .iter()
+            .map(|n| convert(*n))
+            .collect::<Result<Vec<T>, _>>()?;
+        converted
+            .try_into()
+            .map_err(|_| format!("Expected {} numbers", N))
+    }
+}
+
+/// Extracts exactly as many numbers from a line as the output type needs. Tuples and arrays of
+/// any integer type are supported.
+///
+/// ```rust
+/// use advent_of_code_2022::input::numbers::extract;
+///
+/// # fn main() -> Result<(), String> {
+/// let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+/// let (sx, sy, bx, by) = extract::<(i64, i64, i64, i64)>(line)?;
+/// assert_eq!((sx, sy, bx, by), (2, 18, -2, 15));
+///
+/// let [amount, from, to] = extract::<[usize; 3]>("move 1 from 2 to 3")?;
+/// assert_eq!((amount, from, to), (1, 2, 3));
+///
+/// // The wrong amount of numbers is an error
+/// assert!(extract::<(i64, i64)>("1 2 3").is_err());
+///
+/// // As is a number that doesn't fit the type
+/// assert!(extract::<(u8,)>("x=-1").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract<T: FromNumbers>(line: &str) -> Result<T, String> {
+    let mut iter = numbers(line);
+    let mut found = Vec::new();
+    while let Some(s) = iter.next_str() {
+        found.push(
+            s.parse::<i64>()
+                .map_err(|_| format!("Number {} too large in: {}", s, line))?,
+        );
+    }
+    T::from_numbers(&found).map_err(|e| format!("{} in: {}", e, line))
+}
@@ -1,4 +1,6 @@
 extern crate core;
+// Lets code generated by `line-format-derive` refer to this crate by name from inside it
+extern crate self as advent_of_code_2022;
 
 pub mod d01;
 pub mod d02;